@@ -188,7 +188,11 @@ impl Example {
     }
 
     fn init() -> Self {
-        let device = webgpu::get_gpu().request_adapter(None).request_device(None);
+        let device = webgpu::get_gpu()
+            .request_adapter(None)
+            .unwrap()
+            .request_device(None)
+            .unwrap();
         let canvas = mini_canvas::MiniCanvas::new(mini_canvas::CreateDesc {
             height: HEIGHT,
             width: WIDTH,
@@ -308,17 +312,19 @@ impl Example {
         });
 
         let sky_pipeline = device.create_render_pipeline(&webgpu::GpuRenderPipelineDescriptor {
-            // label: Some("Sky"),
+            label: Some("Sky".into()),
             layout: Some(&pipeline_layout),
             vertex: webgpu::GpuVertexState {
                 module: &shader,
                 entry_point: "vs_sky".into(),
+                constants: None,
                 // buffers: None,
                 buffers: Some(vec![]),
             },
             fragment: Some(webgpu::GpuFragmentState {
                 module: &shader,
                 entry_point: "fs_sky".into(),
+                constants: None,
                 targets: vec![webgpu::GpuColorTargetState {
                     format: webgpu::GpuTextureFormat::Bgra8unormSrgb,
                     blend: None,
@@ -352,12 +358,13 @@ impl Example {
         });
 
         let entity_pipeline = device.create_render_pipeline(&webgpu::GpuRenderPipelineDescriptor {
-            // label: Some("Entity"),
+            label: Some("Entity".into()),
             // layout: None,
             layout: Some(&pipeline_layout),
             vertex: webgpu::GpuVertexState {
                 module: &shader,
                 entry_point: "vs_entity".into(),
+                constants: None,
                 buffers: Some(vec![webgpu::GpuVertexBufferLayout {
                     array_stride: std::mem::size_of::<Vertex>() as u64,
                     step_mode: Some(webgpu::GpuVertexStepMode::Vertex),
@@ -379,6 +386,7 @@ impl Example {
             fragment: Some(webgpu::GpuFragmentState {
                 module: &shader,
                 entry_point: "fs_entity".into(),
+                constants: None,
                 targets: vec![webgpu::GpuColorTargetState {
                     format: webgpu::GpuTextureFormat::Bgra8unormSrgb,
                     blend: None,
@@ -1,16 +1,67 @@
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use crate::{
     wasi::webgpu::animation_frame::{FrameEvent, HostFrameListener, Pollable},
-    HostEvent, HostState,
+    HostEvent, HostState, MainThreadAction,
 };
 use tokio::sync::broadcast::Receiver;
 use wasmtime::component::Resource;
 use wasmtime_wasi::preview2::{self, WasiView};
+use winit::monitor::MonitorHandle;
+
+// 60Hz, for when there's no display to ask, like when running headless.
+pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_nanos(16_666_667);
+
+// what frame events report for a canvas on the given monitor.
+pub fn refresh_interval(monitor: Option<MonitorHandle>) -> Duration {
+    monitor
+        .and_then(|monitor| monitor.refresh_rate_millihertz())
+        // some platforms report 0 when they don't know.
+        .filter(|&millihertz| millihertz > 0)
+        .map(|millihertz| Duration::from_nanos(1_000_000_000_000 / millihertz as u64))
+        .unwrap_or(DEFAULT_REFRESH_INTERVAL)
+}
+
+// hands out the frame events, wherever the frames are driven from.
+pub struct FrameClock {
+    start: Instant,
+    frame_index: u64,
+}
+
+impl FrameClock {
+    pub fn new() -> Self {
+        FrameClock {
+            start: Instant::now(),
+            frame_index: 0,
+        }
+    }
+
+    pub fn tick(&mut self, refresh_interval: Duration) -> FrameEvent {
+        let event = FrameEvent {
+            timestamp: self.start.elapsed().as_nanos() as u64,
+            frame_index: self.frame_index,
+            refresh_interval: refresh_interval.as_nanos() as u64,
+        };
+        self.frame_index += 1;
+        event
+    }
+}
+
+impl HostState {
+    // frames come from window redraws, so the next one has to be asked for.
+    fn request_redraws(&self) {
+        if let Some(main_thread) = &self.main_thread {
+            // only fails once the event loop is gone, and there are no frames to wait for anyway.
+            let _ = main_thread.send_event(MainThreadAction::RequestRedraws);
+        }
+    }
+}
 
 impl crate::wasi::webgpu::animation_frame::Host for HostState {
     fn listener(&mut self) -> wasmtime::Result<Resource<AnimationFrameListener>> {
         let receiver = self.sender.subscribe();
+        self.request_redraws();
 
         Ok(self
             .table_mut()
@@ -34,9 +85,15 @@ impl HostFrameListener for HostState {
         frame_listener: Resource<AnimationFrameListener>,
     ) -> wasmtime::Result<Option<FrameEvent>> {
         let frame_listener = self.table.get(&frame_listener).unwrap();
-        Ok(frame_listener.data.lock().unwrap().take())
+        let frame = frame_listener.data.lock().unwrap().take();
+        // asked for whether or not the guest presents this frame, so frames keep coming when it doesn't.
+        if frame.is_some() {
+            self.request_redraws();
+        }
+        Ok(frame)
     }
-    fn drop(&mut self, _self_: Resource<AnimationFrameListener>) -> wasmtime::Result<()> {
+    fn drop(&mut self, listener: Resource<AnimationFrameListener>) -> wasmtime::Result<()> {
+        self.table.delete(listener).unwrap();
         Ok(())
     }
 }
@@ -50,11 +107,9 @@ pub struct AnimationFrameListener {
 impl preview2::Subscribe for AnimationFrameListener {
     async fn ready(&mut self) {
         loop {
-            if let Ok(event) = self.receiver.recv().await {
-                if let HostEvent::Frame = event {
-                    *self.data.lock().unwrap() = Some(FrameEvent { nothing: false });
-                    return;
-                }
+            if let Ok(HostEvent::Frame(event)) = self.receiver.recv().await {
+                *self.data.lock().unwrap() = Some(event);
+                return;
             }
         }
     }
@@ -1,7 +1,9 @@
 use std::mem;
 use std::num::NonZeroU32;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, Weak};
 
+use anyhow::Context;
+use tokio::sync::broadcast::error::RecvError;
 use wasmtime::component::Resource;
 
 use crate::graphics_context::{GraphicsContext, GraphicsContextBuffer, GraphicsContextKind};
@@ -14,6 +16,8 @@ pub struct Surface {
 unsafe impl Send for Surface {}
 unsafe impl Sync for Surface {}
 impl From<softbuffer::Surface> for Surface {
+    // Send and Sync come from the unsafe impls on the wrapper, not the Arc.
+    #[allow(clippy::arc_with_non_send_sync)]
     fn from(surface: softbuffer::Surface) -> Self {
         Surface {
             surface: Arc::new(Mutex::new(surface)),
@@ -21,7 +25,7 @@ impl From<softbuffer::Surface> for Surface {
     }
 }
 impl Surface {
-    pub fn buffer_mut<'a>(&'a mut self) -> FrameBuffer {
+    pub fn buffer_mut(&mut self) -> FrameBuffer {
         let mut surface = self.surface.lock().unwrap();
         let buff = surface.buffer_mut().unwrap();
         // TODO: use ouroboros?
@@ -32,19 +36,58 @@ impl Surface {
     pub fn resize(&mut self, width: NonZeroU32, height: NonZeroU32) {
         self.surface.lock().unwrap().resize(width, height).unwrap();
     }
+
+    pub fn downgrade(&self) -> WeakSurface {
+        WeakSurface(Arc::downgrade(&self.surface))
+    }
+}
+
+// doesn't keep the surface alive.
+pub struct WeakSurface(Weak<Mutex<softbuffer::Surface>>);
+unsafe impl Send for WeakSurface {}
+unsafe impl Sync for WeakSurface {}
+impl WeakSurface {
+    pub fn upgrade(&self) -> Option<Surface> {
+        self.0.upgrade().map(|surface| Surface { surface })
+    }
 }
 
-pub struct FrameBuffer {
+#[derive(Clone)]
+pub enum FrameBuffer {
     // Never none
-    buffer: Arc<Mutex<Option<softbuffer::Buffer<'static>>>>,
+    Surface(Arc<Mutex<Option<softbuffer::Buffer<'static>>>>),
+    // offscreen canvases draw into plain memory that lives as long as their context.
+    Offscreen(Arc<Mutex<Vec<u32>>>),
 }
 unsafe impl Send for FrameBuffer {}
 unsafe impl Sync for FrameBuffer {}
+impl FrameBuffer {
+    pub fn offscreen(width: u32, height: u32) -> anyhow::Result<Self> {
+        let len = (width as usize)
+            .checked_mul(height as usize)
+            .context("offscreen canvas is too large for a frame buffer")?;
+        Ok(FrameBuffer::Offscreen(Arc::new(Mutex::new(vec![0; len]))))
+    }
+
+    // releases the borrow of the surface.
+    pub fn discard(self) {
+        if let FrameBuffer::Surface(buffer) = self {
+            buffer.lock().unwrap().take();
+        }
+    }
+
+    fn with_pixels<T>(&self, f: impl FnOnce(&mut [u32]) -> T) -> T {
+        match self {
+            FrameBuffer::Surface(buffer) => f(buffer.lock().unwrap().as_mut().unwrap()),
+            FrameBuffer::Offscreen(pixels) => f(&mut pixels.lock().unwrap()),
+        }
+    }
+}
 impl From<softbuffer::Buffer<'static>> for FrameBuffer {
+    // Send and Sync come from the unsafe impls on the wrapper, not the Arc.
+    #[allow(clippy::arc_with_non_send_sync)]
     fn from(buffer: softbuffer::Buffer<'static>) -> Self {
-        FrameBuffer {
-            buffer: Arc::new(Mutex::new(Some(buffer))),
-        }
+        FrameBuffer::Surface(Arc::new(Mutex::new(Some(buffer))))
     }
 }
 
@@ -53,10 +96,22 @@ impl crate::wasi::webgpu::frame_buffer::Host for HostState {
         &mut self,
         graphics_context: Resource<GraphicsContext>,
     ) -> wasmtime::Result<()> {
-        let context = unsafe { softbuffer::Context::new(&self.window) }.unwrap();
-        let mut surface = unsafe { softbuffer::Surface::new(&context, &self.window) }.unwrap();
+        let graphics_context_ref = self.table.get_mut(&graphics_context).unwrap();
+        if let Some((width, height)) = graphics_context_ref.offscreen_size {
+            graphics_context_ref.kind = Some(GraphicsContextKind::FrameBufferOffscreen(
+                FrameBuffer::offscreen(width, height)?,
+            ));
+            return Ok(());
+        }
 
-        let size = self.window.inner_size();
+        let window = graphics_context_ref
+            .window
+            .clone()
+            .context("graphics contexts have to be connected to a canvas first")?;
+        let context = unsafe { softbuffer::Context::new(&*window) }.unwrap();
+        let mut surface = unsafe { softbuffer::Surface::new(&context, &*window) }.unwrap();
+
+        let size = window.inner_size();
 
         let _ = surface.resize(
             size.width.try_into().unwrap(),
@@ -66,16 +121,30 @@ impl crate::wasi::webgpu::frame_buffer::Host for HostState {
         let surface: Surface = surface.into();
 
         let mut receiver = self.sender.subscribe();
-        let mut surface_clone = surface.clone();
+        // weak, so the task ends once the context is gone.
+        let surface_weak = surface.downgrade();
+        let window_id = window.id();
 
         tokio::spawn(async move {
             loop {
-                let event = receiver.recv().await.unwrap();
-                if let HostEvent::CanvasResizeEvent(event) = event {
-                    surface_clone.resize(
-                        event.width.try_into().unwrap(),
-                        event.height.try_into().unwrap(),
-                    );
+                let event = match receiver.recv().await {
+                    Ok(event) => event,
+                    // TODO: a missed resize leaves the surface at its old size until the next one.
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return,
+                };
+                let Some(mut surface) = surface_weak.upgrade() else {
+                    return;
+                };
+                if let HostEvent::CanvasResizeEvent(window, event) = event {
+                    // minimized windows have no size, there's nothing to draw then.
+                    if let (true, Ok(width), Ok(height)) = (
+                        window == window_id,
+                        event.width.try_into(),
+                        event.height.try_into(),
+                    ) {
+                        surface.resize(width, height);
+                    }
                 }
             }
         });
@@ -101,32 +170,26 @@ impl crate::wasi::webgpu::frame_buffer::HostFrameBuffer for HostState {
 
     fn length(&mut self, buffer: Resource<FrameBuffer>) -> wasmtime::Result<u32> {
         let buffer = self.table.get(&buffer).unwrap();
-        let len = buffer.buffer.lock().unwrap().as_ref().unwrap().len();
+        let len = buffer.with_pixels(|pixels| pixels.len());
         Ok(len as u32)
     }
 
     fn get(&mut self, buffer: Resource<FrameBuffer>, i: u32) -> wasmtime::Result<u32> {
         let buffer = self.table.get(&buffer).unwrap();
-        let val = *buffer
-            .buffer
-            .lock()
-            .unwrap()
-            .as_ref()
-            .unwrap()
-            .get(i as usize)
-            .unwrap();
-        Ok(val)
+        let val = buffer.with_pixels(|pixels| pixels.get(i as usize).copied());
+        val.context("frame buffer index out of bounds")
     }
 
     fn set(&mut self, buffer: Resource<FrameBuffer>, i: u32, val: u32) -> wasmtime::Result<()> {
         let buffer = self.table.get_mut(&buffer).unwrap();
-        buffer.buffer.lock().unwrap().as_mut().unwrap()[i as usize] = val as u32;
-        Ok(())
+        let pixel =
+            buffer.with_pixels(|pixels| pixels.get_mut(i as usize).map(|pixel| *pixel = val));
+        pixel.context("frame buffer index out of bounds")
     }
 
     fn drop(&mut self, frame_buffer: Resource<FrameBuffer>) -> wasmtime::Result<()> {
         let frame_buffer = self.table.delete(frame_buffer).unwrap();
-        frame_buffer.buffer.lock().unwrap().take();
+        frame_buffer.discard();
         Ok(())
     }
 }
@@ -1,20 +1,51 @@
+use std::sync::{Arc, Mutex};
+
 use crate::{wasi::webgpu::graphics_context::ConfigureContextDesc, HostState};
+use anyhow::Context;
 use wasmtime::component::Resource;
+use winit::window::Window;
 
 // should context be an enum? like: Context::Webgpu2Canvas, Context::Buffer2Canvas.
 
 pub struct GraphicsContext {
     pub kind: Option<GraphicsContextKind>,
+    // the window of the canvas this is connected to.
+    pub window: Option<Arc<Window>>,
+    // set when connected to a canvas that isn't shown in a window, which is every canvas when running headless.
+    pub offscreen_size: Option<(u32, u32)>,
 }
 
 pub enum GraphicsContextKind {
-    Webgpu(wgpu_core::id::SurfaceId),
+    Webgpu {
+        surface: wgpu_core::id::SurfaceId,
+        device: crate::webgpu::Device,
+        // kept so that the surface's textures can report their size and format.
+        config: wgpu_types::SurfaceConfiguration<Vec<wgpu_types::TextureFormat>>,
+        // the canvas' latest size, the surface is reconfigured to it when the next frame is requested.
+        resized: Arc<Mutex<Option<(u32, u32)>>>,
+        // asked for its size when the surface turns out to be outdated.
+        window: Arc<Window>,
+    },
+    // offscreen canvases render into a plain texture that's handed out every frame.
+    WebgpuOffscreen {
+        texture: wgpu_core::id::TextureId,
+        device: crate::webgpu::Device,
+        descriptor: wgpu_core::resource::TextureDescriptor<'static>,
+    },
     FrameBuffer(crate::frame_buffer::Surface),
+    FrameBufferOffscreen(crate::frame_buffer::FrameBuffer),
 }
 
 #[non_exhaustive]
 pub enum GraphicsContextBuffer {
-    Webgpu(wgpu_core::id::TextureId),
+    Webgpu(
+        wgpu_core::id::TextureId,
+        wgpu_core::resource::TextureDescriptor<'static>,
+        // the texture reports its errors to the device the context is connected to.
+        crate::webgpu::ErrorSink,
+        // whether the context owns the texture. Only the invalid stand-in for a skipped frame doesn't.
+        bool,
+    ),
     FrameBuffer(crate::frame_buffer::FrameBuffer),
 }
 
@@ -22,15 +53,30 @@ impl crate::wasi::webgpu::graphics_context::Host for HostState {}
 
 impl crate::wasi::webgpu::graphics_context::HostGraphicsContext for HostState {
     fn new(&mut self) -> wasmtime::Result<Resource<GraphicsContext>> {
-        Ok(self.table.push(GraphicsContext { kind: None }).unwrap())
+        Ok(self
+            .table
+            .push(GraphicsContext {
+                kind: None,
+                window: None,
+                offscreen_size: None,
+            })
+            .unwrap())
     }
 
     fn configure(
         &mut self,
         context: Resource<GraphicsContext>,
-        _desc: ConfigureContextDesc,
+        desc: ConfigureContextDesc,
     ) -> wasmtime::Result<()> {
-        let _context = self.table.get(&context).unwrap();
+        let kind = self
+            .table
+            .get_mut(&context)
+            .unwrap()
+            .kind
+            .take()
+            .context("configure called on an unconnected context")?;
+        let kind = self.configure_context(kind, desc);
+        self.table.get_mut(&context).unwrap().kind = Some(kind);
         Ok(())
     }
 
@@ -38,33 +84,89 @@ impl crate::wasi::webgpu::graphics_context::HostGraphicsContext for HostState {
         &mut self,
         context: Resource<GraphicsContext>,
     ) -> wasmtime::Result<Resource<GraphicsContextBuffer>> {
-        let context_kind = self.table.get_mut(&context).unwrap().kind.as_mut().unwrap();
-        let next_frame = match context_kind {
-            GraphicsContextKind::Webgpu(surface) => {
-                let texture = self
-                    .instance
-                    .surface_get_current_texture::<crate::Backend>(*surface, ())
-                    .unwrap()
-                    .texture_id
-                    .unwrap();
-                GraphicsContextBuffer::Webgpu(texture)
+        // taken out of the table so the surface can be reconfigured while it's borrowed.
+        let mut context_kind = self.table.get_mut(&context).unwrap().kind.take().unwrap();
+        let next_frame = match &mut context_kind {
+            GraphicsContextKind::Webgpu {
+                surface,
+                device,
+                config,
+                resized,
+                window,
+            } => {
+                if let Some((width, height)) = resized.lock().unwrap().take() {
+                    config.width = width.max(1);
+                    config.height = height.max(1);
+                    self.configure_surface(device, *surface, config);
+                }
+                let texture = self.surface_current_texture(device, *surface, config, window);
+                // https://www.w3.org/TR/webgpu/#abstract-opdef-allocate-a-new-context-texture
+                let descriptor = wgpu_types::TextureDescriptor {
+                    label: None,
+                    size: wgpu_types::Extent3d {
+                        width: config.width,
+                        height: config.height,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu_types::TextureDimension::D2,
+                    format: config.format,
+                    usage: config.usage,
+                    view_formats: config.view_formats.clone(),
+                };
+                match texture {
+                    Some(texture) => GraphicsContextBuffer::Webgpu(
+                        texture,
+                        descriptor,
+                        device.error_sink.clone(),
+                        true,
+                    ),
+                    // the guest draws into an invalid texture, and present has nothing to show.
+                    None => GraphicsContextBuffer::Webgpu(
+                        self.create_invalid_texture(device.device, &descriptor),
+                        descriptor,
+                        device.error_sink.clone(),
+                        false,
+                    ),
+                }
             }
+            GraphicsContextKind::WebgpuOffscreen {
+                texture,
+                device,
+                descriptor,
+            } => GraphicsContextBuffer::Webgpu(
+                *texture,
+                descriptor.clone(),
+                device.error_sink.clone(),
+                true,
+            ),
             GraphicsContextKind::FrameBuffer(surface) => {
                 GraphicsContextBuffer::FrameBuffer(surface.buffer_mut())
             }
+            GraphicsContextKind::FrameBufferOffscreen(frame_buffer) => {
+                GraphicsContextBuffer::FrameBuffer(frame_buffer.clone())
+            }
         };
+        self.table.get_mut(&context).unwrap().kind = Some(context_kind);
         Ok(self.table.push_child(next_frame, &context).unwrap())
     }
 
     fn present(&mut self, context: Resource<GraphicsContext>) -> wasmtime::Result<()> {
-        let context = self.table.get(&context).unwrap();
-        match &context.kind {
-            Some(GraphicsContextKind::Webgpu(surface)) => {
-                self.instance
-                    .surface_present::<crate::Backend>(*surface)
-                    .unwrap();
+        let kind = self
+            .table
+            .get(&context)
+            .unwrap()
+            .kind
+            .as_ref()
+            .context("present called on an unconnected context")?;
+        match kind {
+            GraphicsContextKind::Webgpu {
+                surface, device, ..
+            } => {
+                self.present_surface(device, *surface);
             }
-            Some(GraphicsContextKind::FrameBuffer(surface)) => {
+            GraphicsContextKind::FrameBuffer(surface) => {
                 surface
                     .surface
                     .lock()
@@ -74,21 +176,39 @@ impl crate::wasi::webgpu::graphics_context::HostGraphicsContext for HostState {
                     .present()
                     .unwrap();
             }
-            None => {
-                panic!("present called on an unconnected context.");
-            }
+            // nothing to show offscreen canvases on.
+            GraphicsContextKind::WebgpuOffscreen { .. }
+            | GraphicsContextKind::FrameBufferOffscreen(_) => {}
         };
         Ok(())
     }
 
-    fn drop(&mut self, _graphics_context: Resource<GraphicsContext>) -> wasmtime::Result<()> {
-        // todo!()
+    fn drop(&mut self, graphics_context: Resource<GraphicsContext>) -> wasmtime::Result<()> {
+        let graphics_context = self.table.delete(graphics_context).unwrap();
+        match graphics_context.kind {
+            Some(GraphicsContextKind::Webgpu { surface, .. }) => {
+                self.instance.surface_drop(surface);
+            }
+            Some(GraphicsContextKind::WebgpuOffscreen { texture, .. }) => {
+                self.instance.texture_drop::<crate::Backend>(texture, false);
+            }
+            _ => {}
+        }
         Ok(())
     }
 }
 
 impl crate::wasi::webgpu::graphics_context::HostGraphicsContextBuffer for HostState {
-    fn drop(&mut self, _rep: Resource<GraphicsContextBuffer>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(&mut self, buffer: Resource<GraphicsContextBuffer>) -> wasmtime::Result<()> {
+        // only buffers that were never turned into a texture or frame buffer end up here.
+        match self.table.delete(buffer).unwrap() {
+            // the surface texture is released when the context presents, and offscreen textures when the context is dropped.
+            GraphicsContextBuffer::Webgpu(_, _, _, true) => {}
+            GraphicsContextBuffer::Webgpu(texture, _, _, false) => {
+                self.instance.texture_drop::<crate::Backend>(texture, false);
+            }
+            GraphicsContextBuffer::FrameBuffer(frame_buffer) => frame_buffer.discard(),
+        }
+        Ok(())
     }
 }
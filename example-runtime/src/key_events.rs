@@ -2,7 +2,7 @@ use std::sync::Mutex;
 
 use crate::{
     wasi::webgpu::key_events::{KeyEvent, Pollable},
-    HostEvent, HostState,
+    EventFilter, HostEvent, HostState,
 };
 use tokio::sync::broadcast::Receiver;
 use wasmtime::component::Resource;
@@ -13,10 +13,7 @@ impl crate::wasi::webgpu::key_events::Host for HostState {
         let receiver = self.sender.subscribe();
         Ok(self
             .table_mut()
-            .push(KeyUpListener {
-                receiver,
-                data: Default::default(),
-            })
+            .push(KeyUpListener::new(receiver, EventFilter::AnyWindow))
             .unwrap())
     }
 
@@ -24,10 +21,7 @@ impl crate::wasi::webgpu::key_events::Host for HostState {
         let receiver = self.sender.subscribe();
         Ok(self
             .table_mut()
-            .push(KeyDownListener {
-                receiver,
-                data: Default::default(),
-            })
+            .push(KeyDownListener::new(receiver, EventFilter::AnyWindow))
             .unwrap())
     }
 }
@@ -43,7 +37,8 @@ impl crate::wasi::webgpu::key_events::HostKeyUpListener for HostState {
         let key_up = self.table.get(&key_up).unwrap();
         Ok(key_up.data.lock().unwrap().take())
     }
-    fn drop(&mut self, _self_: Resource<KeyUpListener>) -> wasmtime::Result<()> {
+    fn drop(&mut self, listener: Resource<KeyUpListener>) -> wasmtime::Result<()> {
+        self.table.delete(listener).unwrap();
         Ok(())
     }
 }
@@ -51,17 +46,30 @@ impl crate::wasi::webgpu::key_events::HostKeyUpListener for HostState {
 #[derive(Debug)]
 pub struct KeyUpListener {
     receiver: Receiver<HostEvent>,
+    filter: EventFilter,
     data: Mutex<Option<KeyEvent>>,
 }
 
+impl KeyUpListener {
+    pub fn new(receiver: Receiver<HostEvent>, filter: EventFilter) -> Self {
+        KeyUpListener {
+            receiver,
+            filter,
+            data: Default::default(),
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl preview2::Subscribe for KeyUpListener {
     async fn ready(&mut self) {
         loop {
             let event = self.receiver.recv().await.unwrap();
-            if let HostEvent::KeyUpEvent(event) = event {
-                *self.data.lock().unwrap() = Some(event);
-                return;
+            if let HostEvent::KeyUpEvent(window, event) = event {
+                if self.filter.matches(window) {
+                    *self.data.lock().unwrap() = Some(event);
+                    return;
+                }
             }
         }
     }
@@ -78,7 +86,8 @@ impl crate::wasi::webgpu::key_events::HostKeyDownListener for HostState {
         let key_down = self.table.get(&key_down).unwrap();
         Ok(key_down.data.lock().unwrap().take())
     }
-    fn drop(&mut self, _self_: Resource<KeyDownListener>) -> wasmtime::Result<()> {
+    fn drop(&mut self, listener: Resource<KeyDownListener>) -> wasmtime::Result<()> {
+        self.table.delete(listener).unwrap();
         Ok(())
     }
 }
@@ -86,17 +95,30 @@ impl crate::wasi::webgpu::key_events::HostKeyDownListener for HostState {
 #[derive(Debug)]
 pub struct KeyDownListener {
     receiver: Receiver<HostEvent>,
+    filter: EventFilter,
     data: Mutex<Option<KeyEvent>>,
 }
 
+impl KeyDownListener {
+    pub fn new(receiver: Receiver<HostEvent>, filter: EventFilter) -> Self {
+        KeyDownListener {
+            receiver,
+            filter,
+            data: Default::default(),
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl preview2::Subscribe for KeyDownListener {
     async fn ready(&mut self) {
         loop {
             let event = self.receiver.recv().await.unwrap();
-            if let HostEvent::KeyDownEvent(event) = event {
-                *self.data.lock().unwrap() = Some(event);
-                return;
+            if let HostEvent::KeyDownEvent(window, event) = event {
+                if self.filter.matches(window) {
+                    *self.data.lock().unwrap() = Some(event);
+                    return;
+                }
             }
         }
     }
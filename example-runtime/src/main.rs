@@ -1,17 +1,28 @@
-use std::time::Duration;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+    time::{Duration, Instant},
+};
 
 use anyhow::Context;
 use clap::Parser;
 use wasi::webgpu::{
-    key_events::KeyEvent, mini_canvas::ResizeEvent, pointer_events::PointerEvent,
+    animation_frame::FrameEvent, key_events::KeyEvent, mini_canvas::ResizeEvent,
+    pointer_events::PointerEvent,
 };
 use tokio::sync::broadcast::Sender;
 use wasmtime::{
     component::{Component, Linker},
     Config, Engine, Store,
 };
-use winit::{event::ElementState, event_loop::EventLoop, window::Window};
+use winit::{
+    dpi::PhysicalSize,
+    event::{ElementState, StartCause},
+    event_loop::{EventLoop, EventLoopProxy},
+    window::{Window, WindowBuilder, WindowId},
+};
 
+use crate::animation_frame::{refresh_interval, FrameClock, DEFAULT_REFRESH_INTERVAL};
 use wasmtime_wasi::preview2::{self, ResourceTable, WasiCtx, WasiCtxBuilder, WasiView};
 mod animation_frame;
 mod frame_buffer;
@@ -44,10 +55,20 @@ struct RuntimeArgs {
     /// The example name
     #[arg(long)]
     example: String,
+
+    /// Only ever hand out the fallback (software) adapter. Useful on machines without a GPU, like CI.
+    #[arg(long)]
+    force_fallback_adapter: bool,
+
+    /// Run without a window. Every canvas is drawn offscreen, so guests can run unattended on servers and CI.
+    #[arg(long)]
+    headless: bool,
 }
 
 // needed for wasmtime::component::bindgen! as it only looks in the current crate.
+#[allow(clippy::single_component_path_imports)]
 pub(crate) use wgpu_core;
+#[allow(clippy::single_component_path_imports)]
 pub(crate) use wgpu_types;
 
 wasmtime::component::bindgen!({
@@ -56,30 +77,68 @@ wasmtime::component::bindgen!({
     async: {
         only_imports: [
             "poll",
+            // waits for the main thread to create a window.
+            "[constructor]mini-canvas",
         ],
     },
     with: {
         "wasi:io/poll": preview2::bindings::io::poll,
         "wasi:io/streams": preview2::bindings::io::streams,
         "wasi:webgpu/webgpu/gpu-adapter": wgpu_core::id::AdapterId,
+        "wasi:webgpu/webgpu/gpu-adapter-info": wgpu_types::AdapterInfo,
         "wasi:webgpu/webgpu/gpu-device": webgpu::Device,
         // queue is same as device
         "wasi:webgpu/webgpu/gpu-queue": webgpu::Device,
-        "wasi:webgpu/webgpu/gpu-command-encoder": wgpu_core::id::CommandEncoderId,
-        "wasi:webgpu/webgpu/gpu-render-pass-encoder": wgpu_core::command::RenderPass,
-        "wasi:webgpu/webgpu/gpu-shader-module": wgpu_core::id::ShaderModuleId,
-        "wasi:webgpu/webgpu/gpu-render-pipeline": wgpu_core::id::RenderPipelineId,
-        "wasi:webgpu/webgpu/gpu-command-buffer": wgpu_core::id::CommandBufferId,
+        // "wasi:webgpu/webgpu/gpu-command-encoder": wgpu_core::id::CommandEncoderId,
+        "wasi:webgpu/webgpu/gpu-command-encoder": webgpu::CommandEncoder,
+        // "wasi:webgpu/webgpu/gpu-render-pass-encoder": wgpu_core::command::RenderPass,
+        "wasi:webgpu/webgpu/gpu-render-pass-encoder": webgpu::RenderPassEncoder,
+        "wasi:webgpu/webgpu/gpu-render-bundle-encoder": webgpu::RenderBundleEncoder,
+        // "wasi:webgpu/webgpu/gpu-render-bundle": wgpu_core::id::RenderBundleId,
+        "wasi:webgpu/webgpu/gpu-render-bundle": webgpu::RenderBundle,
+        // "wasi:webgpu/webgpu/gpu-shader-module": wgpu_core::id::ShaderModuleId,
+        "wasi:webgpu/webgpu/gpu-shader-module": webgpu::ShaderModule,
+        "wasi:webgpu/webgpu/gpu-compilation-info": webgpu::CompilationInfo,
+        "wasi:webgpu/webgpu/gpu-compilation-message": webgpu::CompilationMessage,
+        // "wasi:webgpu/webgpu/gpu-render-pipeline": wgpu_core::id::RenderPipelineId,
+        "wasi:webgpu/webgpu/gpu-render-pipeline": webgpu::RenderPipeline,
+        "wasi:webgpu/webgpu/gpu-render-pipeline-future": webgpu::RenderPipelineFuture,
+        "wasi:webgpu/webgpu/gpu-compute-pass-encoder": webgpu::ComputePassEncoder,
+        // "wasi:webgpu/webgpu/gpu-compute-pipeline": wgpu_core::id::ComputePipelineId,
+        "wasi:webgpu/webgpu/gpu-compute-pipeline": webgpu::ComputePipeline,
+        "wasi:webgpu/webgpu/gpu-compute-pipeline-future": webgpu::ComputePipelineFuture,
+        "wasi:webgpu/webgpu/gpu-pipeline-error": webgpu::PipelineError,
+        // "wasi:webgpu/webgpu/gpu-command-buffer": wgpu_core::id::CommandBufferId,
+        "wasi:webgpu/webgpu/gpu-command-buffer": webgpu::CommandBuffer,
         // "wasi:webgpu/webgpu/gpu-buffer": wgpu_core::id::BufferId,
         "wasi:webgpu/webgpu/gpu-buffer": webgpu::Buffer,
-        "wasi:webgpu/webgpu/remote-buffer": webgpu::Buffer,
-        "wasi:webgpu/webgpu/gpu-pipeline-layout": wgpu_core::id::PipelineLayoutId,
-        "wasi:webgpu/webgpu/gpu-bind-group-layout": wgpu_core::id::BindGroupLayoutId,
-        "wasi:webgpu/webgpu/gpu-sampler": wgpu_core::id::SamplerId,
+        "wasi:webgpu/webgpu/remote-buffer": webgpu::MappedRange,
+        "wasi:webgpu/webgpu/gpu-buffer-map-future": webgpu::BufferMapFuture,
+        // "wasi:webgpu/webgpu/gpu-pipeline-layout": wgpu_core::id::PipelineLayoutId,
+        "wasi:webgpu/webgpu/gpu-pipeline-layout": webgpu::PipelineLayout,
+        // "wasi:webgpu/webgpu/gpu-bind-group-layout": wgpu_core::id::BindGroupLayoutId,
+        "wasi:webgpu/webgpu/gpu-bind-group-layout": webgpu::BindGroupLayout,
+        // "wasi:webgpu/webgpu/gpu-sampler": wgpu_core::id::SamplerId,
+        "wasi:webgpu/webgpu/gpu-sampler": webgpu::Sampler,
         "wasi:webgpu/webgpu/gpu-supported-features": wgpu_types::Features,
-        "wasi:webgpu/webgpu/gpu-texture": wgpu_core::id::TextureId,
-        "wasi:webgpu/webgpu/gpu-bind-group": wgpu_core::id::BindGroupId,
-        "wasi:webgpu/webgpu/gpu-texture-view": wgpu_core::id::TextureViewId,
+        "wasi:webgpu/webgpu/gpu-supported-limits": wgpu_types::Limits,
+        "wasi:webgpu/webgpu/wgsl-language-features": webgpu::WgslLanguageFeatures,
+        // "wasi:webgpu/webgpu/gpu-texture": wgpu_core::id::TextureId,
+        "wasi:webgpu/webgpu/gpu-texture": webgpu::Texture,
+        // "wasi:webgpu/webgpu/gpu-bind-group": wgpu_core::id::BindGroupId,
+        "wasi:webgpu/webgpu/gpu-bind-group": webgpu::BindGroup,
+        // "wasi:webgpu/webgpu/gpu-texture-view": wgpu_core::id::TextureViewId,
+        "wasi:webgpu/webgpu/gpu-texture-view": webgpu::TextureView,
+        "wasi:webgpu/webgpu/gpu-query-set": webgpu::QuerySet,
+        // all error kinds share one host type, since wit has no inheritance.
+        "wasi:webgpu/webgpu/gpu-error": webgpu::Error,
+        "wasi:webgpu/webgpu/gpu-validation-error": webgpu::Error,
+        "wasi:webgpu/webgpu/gpu-out-of-memory-error": webgpu::Error,
+        "wasi:webgpu/webgpu/gpu-internal-error": webgpu::Error,
+        "wasi:webgpu/webgpu/gpu-uncaptured-error-event": webgpu::UncapturedErrorEvent,
+        "wasi:webgpu/webgpu/gpu-uncaptured-error-listener": webgpu::UncapturedErrorListener,
+        "wasi:webgpu/webgpu/gpu-device-lost-info": webgpu::DeviceLostInfo,
+        "wasi:webgpu/webgpu/gpu-device-lost-future": webgpu::DeviceLostFuture,
         "wasi:webgpu/frame-buffer/frame-buffer": frame_buffer::FrameBuffer,
         "wasi:webgpu/pointer-events/pointer-up-listener": pointer_events::PointerUpListener,
         "wasi:webgpu/pointer-events/pointer-down-listener": pointer_events::PointerDownListener,
@@ -98,106 +157,209 @@ struct HostState {
     pub table: ResourceTable,
     pub ctx: WasiCtx,
     pub sender: Sender<HostEvent>,
-    pub instance: wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>,
-    pub window: Window,
+    pub instance: Arc<wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>>,
+    // none when running headless.
+    pub main_thread: Option<EventLoopProxy<MainThreadAction>>,
+    pub force_fallback_adapter: bool,
+    // finding it out takes an adapter, so it's only done once.
+    pub preferred_canvas_format: wgpu_types::TextureFormat,
 }
 
-pub fn listen_to_events(event_loop: EventLoop<()>, sender: Sender<HostEvent>) {
-    use winit::event::{Event, MouseButton, WindowEvent};
+// winit only creates windows on the event loop's thread, so the guest has to ask it to.
+pub enum MainThreadAction {
+    CreateWindow {
+        width: u32,
+        height: u32,
+        reply: tokio::sync::oneshot::Sender<Arc<Window>>,
+    },
+    // asks every canvas' window for its next frame.
+    RequestRedraws,
+}
+
+// which windows a listener wants events from.
+#[derive(Clone, Copy, Debug)]
+pub enum EventFilter {
+    AnyWindow,
+    Window(WindowId),
+    // offscreen canvases never get any events.
+    NoWindow,
+}
 
-    let sender_2 = sender.clone();
+impl EventFilter {
+    pub fn matches(&self, window: WindowId) -> bool {
+        match self {
+            EventFilter::AnyWindow => true,
+            EventFilter::Window(id) => *id == window,
+            EventFilter::NoWindow => false,
+        }
+    }
+}
+
+// without a display there's nothing to sync to.
+pub fn spawn_frame_ticker(sender: Sender<HostEvent>) {
     tokio::spawn(async move {
+        let mut frame_clock = FrameClock::new();
+        let mut interval = tokio::time::interval(DEFAULT_REFRESH_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
         loop {
-            // winit doesn't provide frame callbacks.
-            sender_2.send(HostEvent::Frame).unwrap();
-            tokio::time::sleep(Duration::from_millis(16)).await;
+            interval.tick().await;
+            sender
+                .send(HostEvent::Frame(frame_clock.tick(DEFAULT_REFRESH_INTERVAL)))
+                .unwrap();
         }
     });
+}
 
-    let mut pointer_x: f64 = 0.0;
-    let mut pointer_y: f64 = 0.0;
+pub fn listen_to_events(event_loop: EventLoop<MainThreadAction>, sender: Sender<HostEvent>) {
+    use winit::event::{Event, MouseButton, WindowEvent};
 
-    event_loop.run(move |event, _target, _control_flow| {
-        // *control_flow = ControlFlow::Poll;
+    let mut frame_clock = FrameClock::new();
+    let mut next_frame = Instant::now();
+    // set by the first window redrawn since the last frame, so several windows still make one frame.
+    let mut redrawn: Option<Duration> = None;
+    // canvases get their frames from redraws. Weak, so windows still close when the guest drops them.
+    let mut windows: HashMap<WindowId, Weak<Window>> = HashMap::new();
+
+    // where the pointer last was in each window, since button events don't say.
+    let mut pointers: HashMap<WindowId, PointerEvent> = HashMap::new();
+
+    event_loop.run(move |event, target, control_flow| {
         match event {
+            // guests that only draw offscreen have no redraws to wait for, so they get frames at the default rate.
+            Event::NewEvents(StartCause::Init | StartCause::ResumeTimeReached { .. }) => {
+                windows.retain(|_, window| window.strong_count() > 0);
+                if windows.is_empty() {
+                    sender
+                        .send(HostEvent::Frame(frame_clock.tick(DEFAULT_REFRESH_INTERVAL)))
+                        .unwrap();
+                }
+                // frames that were missed are skipped rather than sent all at once.
+                next_frame = (next_frame + DEFAULT_REFRESH_INTERVAL).max(Instant::now());
+                control_flow.set_wait_until(next_frame);
+            }
+            // requested by frame listeners once the guest takes a frame, so the next one comes as soon as the guest is ready for it.
+            Event::RedrawRequested(window_id) => {
+                if let Some(window) = windows.get(&window_id).and_then(Weak::upgrade) {
+                    redrawn.get_or_insert_with(|| refresh_interval(window.current_monitor()));
+                }
+            }
+            // winit hands out every window's redraw before this, so it comes once per refresh.
+            Event::RedrawEventsCleared => {
+                if let Some(refresh_interval) = redrawn.take() {
+                    sender
+                        .send(HostEvent::Frame(frame_clock.tick(refresh_interval)))
+                        .unwrap();
+                }
+            }
+            Event::UserEvent(MainThreadAction::RequestRedraws) => {
+                windows.retain(|_, window| window.strong_count() > 0);
+                pointers.retain(|window_id, _| windows.contains_key(window_id));
+                for window in windows.values().filter_map(Weak::upgrade) {
+                    window.request_redraw();
+                }
+            }
+            Event::UserEvent(MainThreadAction::CreateWindow {
+                width,
+                height,
+                reply,
+            }) => {
+                let window = Arc::new(
+                    WindowBuilder::new()
+                        .with_inner_size(PhysicalSize::new(width, height))
+                        .build(target)
+                        .unwrap(),
+                );
+                window.request_redraw();
+                windows.insert(window.id(), Arc::downgrade(&window));
+                // only fails if the guest is gone, and the window with it.
+                let _ = reply.send(window);
+            }
             Event::WindowEvent {
                 event: WindowEvent::CloseRequested,
                 ..
             } => {}
             Event::WindowEvent {
+                window_id,
                 event: WindowEvent::Resized(new_size),
-                ..
             } => {
                 sender
-                    .send(HostEvent::CanvasResizeEvent(ResizeEvent {
-                        height: new_size.height,
-                        width: new_size.width,
-                    }))
+                    .send(HostEvent::CanvasResizeEvent(
+                        window_id,
+                        ResizeEvent {
+                            height: new_size.height,
+                            width: new_size.width,
+                        },
+                    ))
                     .unwrap();
             }
             Event::WindowEvent {
+                window_id,
                 event:
                     WindowEvent::MouseInput {
                         button: MouseButton::Left,
                         state,
                         ..
                     },
-                ..
             } => {
+                let pointer = pointers
+                    .get(&window_id)
+                    .copied()
+                    .unwrap_or(PointerEvent { x: 0.0, y: 0.0 });
                 let event = match state {
-                    ElementState::Pressed => HostEvent::PointerDownEvent(PointerEvent {
-                        x: pointer_x,
-                        y: pointer_y,
-                    }),
-                    ElementState::Released => HostEvent::PointerUpEvent(PointerEvent {
-                        x: pointer_x,
-                        y: pointer_y,
-                    }),
+                    ElementState::Pressed => HostEvent::PointerDownEvent(window_id, pointer),
+                    ElementState::Released => HostEvent::PointerUpEvent(window_id, pointer),
                 };
                 sender.send(event).unwrap();
             }
             Event::WindowEvent {
+                window_id,
                 event: WindowEvent::KeyboardInput { input, .. },
-                ..
             } => {
                 #[allow(deprecated)]
                 let event = match input.state {
-                    ElementState::Pressed => HostEvent::KeyDownEvent(KeyEvent {
-                        code: input
-                            .virtual_keycode
-                            .map(|k| format!("{k:?}"))
-                            .unwrap_or_default(),
-                        key: input.scancode.to_string(),
-                        alt_key: input.modifiers.shift(),
-                        ctrl_key: input.modifiers.ctrl(),
-                        meta_key: input.modifiers.logo(),
-                        shift_key: input.modifiers.shift(),
-                    }),
-                    ElementState::Released => HostEvent::KeyUpEvent(KeyEvent {
-                        code: input
-                            .virtual_keycode
-                            .map(|k| format!("{k:?}"))
-                            .unwrap_or_default(),
-                        key: input.scancode.to_string(),
-                        alt_key: input.modifiers.shift(),
-                        ctrl_key: input.modifiers.ctrl(),
-                        meta_key: input.modifiers.logo(),
-                        shift_key: input.modifiers.shift(),
-                    }),
+                    ElementState::Pressed => HostEvent::KeyDownEvent(
+                        window_id,
+                        KeyEvent {
+                            code: input
+                                .virtual_keycode
+                                .map(|k| format!("{k:?}"))
+                                .unwrap_or_default(),
+                            key: input.scancode.to_string(),
+                            alt_key: input.modifiers.shift(),
+                            ctrl_key: input.modifiers.ctrl(),
+                            meta_key: input.modifiers.logo(),
+                            shift_key: input.modifiers.shift(),
+                        },
+                    ),
+                    ElementState::Released => HostEvent::KeyUpEvent(
+                        window_id,
+                        KeyEvent {
+                            code: input
+                                .virtual_keycode
+                                .map(|k| format!("{k:?}"))
+                                .unwrap_or_default(),
+                            key: input.scancode.to_string(),
+                            alt_key: input.modifiers.shift(),
+                            ctrl_key: input.modifiers.ctrl(),
+                            meta_key: input.modifiers.logo(),
+                            shift_key: input.modifiers.shift(),
+                        },
+                    ),
                 };
                 sender.send(event).unwrap();
             }
             Event::WindowEvent {
+                window_id,
                 event: WindowEvent::CursorMoved { position, .. },
-                ..
             } => {
-                pointer_x = position.x;
-                pointer_y = position.y;
-                let event = HostEvent::PointerMoveEvent(PointerEvent {
-                    x: pointer_x,
-                    y: pointer_y,
-                });
-                sender.send(event).unwrap();
+                let pointer = PointerEvent {
+                    x: position.x,
+                    y: position.y,
+                };
+                pointers.insert(window_id, pointer);
+                sender
+                    .send(HostEvent::PointerMoveEvent(window_id, pointer))
+                    .unwrap();
             }
             _ => (),
         }
@@ -205,22 +367,88 @@ pub fn listen_to_events(event_loop: EventLoop<()>, sender: Sender<HostEvent>) {
 }
 
 impl HostState {
-    fn new(event_loop: &EventLoop<()>, sender: Sender<HostEvent>) -> Self {
+    fn new(
+        main_thread: Option<EventLoopProxy<MainThreadAction>>,
+        sender: Sender<HostEvent>,
+        force_fallback_adapter: bool,
+    ) -> Self {
+        let instance = Arc::new(wgpu_core::global::Global::new(
+            "webgpu",
+            wgpu_core::identity::IdentityManagerFactory,
+            wgpu_types::InstanceDescriptor {
+                backends: wgpu_types::Backends::all(),
+                flags: wgpu_types::InstanceFlags::from_build_config(),
+                dx12_shader_compiler: wgpu_types::Dx12Compiler::Fxc,
+                gles_minor_version: wgpu_types::Gles3MinorVersion::default(),
+            },
+        ));
+        let preferred_canvas_format =
+            webgpu::preferred_canvas_format(&instance, force_fallback_adapter);
         Self {
             table: ResourceTable::new(),
             ctx: WasiCtxBuilder::new().inherit_stdio().build(),
             sender,
-            instance: wgpu_core::global::Global::new(
-                "webgpu",
-                wgpu_core::identity::IdentityManagerFactory,
-                wgpu_types::InstanceDescriptor {
-                    backends: wgpu_types::Backends::all(),
-                    flags: wgpu_types::InstanceFlags::from_build_config(),
-                    dx12_shader_compiler: wgpu_types::Dx12Compiler::Fxc,
-                    gles_minor_version: wgpu_types::Gles3MinorVersion::default(),
-                },
-            ),
-            window: Window::new(event_loop).unwrap(),
+            instance,
+            main_thread,
+            force_fallback_adapter,
+            preferred_canvas_format,
+        }
+    }
+
+    // none when running headless.
+    // the future doesn't borrow self, since HostState isn't Sync and async host calls have to be Send.
+    pub fn create_window(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> impl std::future::Future<Output = Option<Arc<Window>>> {
+        let main_thread = self.main_thread.clone();
+        async move {
+            let main_thread = main_thread?;
+            let (reply, window) = tokio::sync::oneshot::channel();
+            main_thread
+                .send_event(MainThreadAction::CreateWindow {
+                    width,
+                    height,
+                    reply,
+                })
+                .ok()?;
+            window.await.ok()
+        }
+    }
+}
+
+// Lists the wgpu-core objects that outlive the store, which means something forgot to release them.
+// only called once the store, and the resource table with it, has been dropped.
+fn report_leaks(instance: &wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>) {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    // lets wgpu-core free whatever was dropped but is still waiting on the gpu.
+    let _ = instance.poll_all_devices(true);
+    let surfaces = instance.generate_report().surfaces;
+    let report = <Backend as wgpu_core::hal_api::HalApi>::hub(instance).generate_report();
+    let storages = [
+        ("surfaces", surfaces),
+        ("adapters", report.adapters),
+        ("devices", report.devices),
+        ("pipeline layouts", report.pipeline_layouts),
+        ("shader modules", report.shader_modules),
+        ("bind group layouts", report.bind_group_layouts),
+        ("bind groups", report.bind_groups),
+        ("command buffers", report.command_buffers),
+        ("render bundles", report.render_bundles),
+        ("render pipelines", report.render_pipelines),
+        ("compute pipelines", report.compute_pipelines),
+        ("query sets", report.query_sets),
+        ("buffers", report.buffers),
+        ("textures", report.textures),
+        ("texture views", report.texture_views),
+        ("samplers", report.samplers),
+    ];
+    for (kind, storage) in storages {
+        if storage.num_occupied > 0 {
+            log::warn!("leaked {} webgpu {kind}", storage.num_occupied);
         }
     }
 }
@@ -253,13 +481,14 @@ impl ExampleImports for HostState {
 
 #[derive(Clone, Debug)]
 pub enum HostEvent {
-    PointerUpEvent(PointerEvent),
-    PointerDownEvent(PointerEvent),
-    PointerMoveEvent(PointerEvent),
-    KeyUpEvent(KeyEvent),
-    KeyDownEvent(KeyEvent),
-    CanvasResizeEvent(ResizeEvent),
-    Frame,
+    PointerUpEvent(WindowId, PointerEvent),
+    PointerDownEvent(WindowId, PointerEvent),
+    PointerMoveEvent(WindowId, PointerEvent),
+    KeyUpEvent(WindowId, KeyEvent),
+    KeyDownEvent(WindowId, KeyEvent),
+    CanvasResizeEvent(WindowId, ResizeEvent),
+    Frame(FrameEvent),
+    UncapturedGpuError(wgpu_core::id::DeviceId, webgpu::Error),
 }
 
 #[tokio::main]
@@ -292,9 +521,14 @@ async fn main() -> anyhow::Result<()> {
 
     Example::add_root_to_linker(&mut linker, |state: &mut HostState| state)?;
 
-    let event = winit::event_loop::EventLoopBuilder::new().build();
+    let event =
+        (!args.headless).then(|| winit::event_loop::EventLoopBuilder::with_user_event().build());
 
-    let host_state = HostState::new(&event, sender.clone());
+    let host_state = HostState::new(
+        event.as_ref().map(|event| event.create_proxy()),
+        sender.clone(),
+        args.force_fallback_adapter,
+    );
 
     let mut store = Store::new(&engine, host_state);
 
@@ -307,11 +541,22 @@ async fn main() -> anyhow::Result<()> {
         .await
         .unwrap();
 
-    tokio::spawn(async move {
+    let guest = tokio::spawn(async move {
         instance.call_start(&mut store).await.unwrap();
+        store
     });
 
-    listen_to_events(event, sender);
+    match event {
+        Some(event) => listen_to_events(event, sender),
+        None => {
+            // nothing to listen to without a window, so just keep the frames coming until the guest is done.
+            spawn_frame_ticker(sender);
+            let store = guest.await?;
+            let instance = Arc::clone(&store.data().instance);
+            drop(store);
+            report_leaks(&instance);
+        }
+    }
 
     Ok(())
 }
@@ -1,30 +1,51 @@
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 use crate::{
+    key_events::{KeyDownListener, KeyUpListener},
+    pointer_events::{PointerDownListener, PointerMoveListener, PointerUpListener},
     wasi::webgpu::mini_canvas::{CreateDesc, GraphicsContext, Pollable, ResizeEvent},
-    HostEvent, HostState,
+    EventFilter, HostEvent, HostState,
 };
 use tokio::sync::broadcast::Receiver;
 use wasmtime::component::Resource;
 use wasmtime_wasi::preview2::{self, WasiView};
+use winit::window::Window;
 
 #[derive(Debug)]
 pub struct MiniCanvas {
     pub height: u32,
     pub width: u32,
     pub offscreen: bool,
+    // none for offscreen canvases, which is every canvas when running headless.
+    pub window: Option<Arc<Window>>,
+}
+
+impl MiniCanvas {
+    fn event_filter(&self) -> EventFilter {
+        match &self.window {
+            Some(window) => EventFilter::Window(window.id()),
+            None => EventFilter::NoWindow,
+        }
+    }
 }
 
 impl crate::wasi::webgpu::mini_canvas::Host for HostState {}
 
+#[async_trait::async_trait]
 impl crate::wasi::webgpu::mini_canvas::HostMiniCanvas for HostState {
-    fn new(&mut self, desc: CreateDesc) -> wasmtime::Result<Resource<MiniCanvas>> {
+    async fn new(&mut self, desc: CreateDesc) -> wasmtime::Result<Resource<MiniCanvas>> {
+        let window = if desc.offscreen {
+            None
+        } else {
+            self.create_window(desc.width, desc.height).await
+        };
         Ok(self
             .table
             .push(MiniCanvas {
                 height: desc.height,
                 width: desc.width,
                 offscreen: desc.offscreen,
+                window,
             })
             .unwrap())
     }
@@ -34,37 +55,113 @@ impl crate::wasi::webgpu::mini_canvas::HostMiniCanvas for HostState {
         mini_canvas: Resource<MiniCanvas>,
         context: Resource<GraphicsContext>,
     ) -> wasmtime::Result<()> {
-        let _mini_canvas = self.table.get(&mini_canvas).unwrap();
-        let _context = self.table.get(&context).unwrap();
-        // noop for now, will do the surface creation once window is part min-canvas instead of a singleton.
+        let mini_canvas = self.table.get(&mini_canvas).unwrap();
+        let window = mini_canvas.window.clone();
+        let offscreen_size = match window {
+            Some(_) => None,
+            None => Some((mini_canvas.width, mini_canvas.height)),
+        };
+        // the surface itself is created when connecting the context to webgpu or a frame buffer, which is when we know which one it needs.
+        let context = self.table.get_mut(&context).unwrap();
+        context.window = window;
+        context.offscreen_size = offscreen_size;
         Ok(())
     }
 
     fn resize_listener(
         &mut self,
-        _mini_canvas: Resource<MiniCanvas>,
+        mini_canvas: Resource<MiniCanvas>,
     ) -> wasmtime::Result<Resource<ResizeListener>> {
+        let filter = self.table.get(&mini_canvas).unwrap().event_filter();
         let receiver = self.sender.subscribe();
         Ok(self
             .table_mut()
             .push(ResizeListener {
                 receiver,
+                filter,
                 data: Default::default(),
             })
             .unwrap())
     }
 
+    fn pointer_up_listener(
+        &mut self,
+        mini_canvas: Resource<MiniCanvas>,
+    ) -> wasmtime::Result<Resource<PointerUpListener>> {
+        let filter = self.table.get(&mini_canvas).unwrap().event_filter();
+        let receiver = self.sender.subscribe();
+        Ok(self
+            .table_mut()
+            .push(PointerUpListener::new(receiver, filter))
+            .unwrap())
+    }
+
+    fn pointer_down_listener(
+        &mut self,
+        mini_canvas: Resource<MiniCanvas>,
+    ) -> wasmtime::Result<Resource<PointerDownListener>> {
+        let filter = self.table.get(&mini_canvas).unwrap().event_filter();
+        let receiver = self.sender.subscribe();
+        Ok(self
+            .table_mut()
+            .push(PointerDownListener::new(receiver, filter))
+            .unwrap())
+    }
+
+    fn pointer_move_listener(
+        &mut self,
+        mini_canvas: Resource<MiniCanvas>,
+    ) -> wasmtime::Result<Resource<PointerMoveListener>> {
+        let filter = self.table.get(&mini_canvas).unwrap().event_filter();
+        let receiver = self.sender.subscribe();
+        Ok(self
+            .table_mut()
+            .push(PointerMoveListener::new(receiver, filter))
+            .unwrap())
+    }
+
+    fn key_up_listener(
+        &mut self,
+        mini_canvas: Resource<MiniCanvas>,
+    ) -> wasmtime::Result<Resource<KeyUpListener>> {
+        let filter = self.table.get(&mini_canvas).unwrap().event_filter();
+        let receiver = self.sender.subscribe();
+        Ok(self
+            .table_mut()
+            .push(KeyUpListener::new(receiver, filter))
+            .unwrap())
+    }
+
+    fn key_down_listener(
+        &mut self,
+        mini_canvas: Resource<MiniCanvas>,
+    ) -> wasmtime::Result<Resource<KeyDownListener>> {
+        let filter = self.table.get(&mini_canvas).unwrap().event_filter();
+        let receiver = self.sender.subscribe();
+        Ok(self
+            .table_mut()
+            .push(KeyDownListener::new(receiver, filter))
+            .unwrap())
+    }
+
     fn height(&mut self, mini_canvas: Resource<MiniCanvas>) -> wasmtime::Result<u32> {
-        let _mini_canvas = self.table.get(&mini_canvas).unwrap();
-        Ok(self.window.inner_size().height)
+        let mini_canvas = self.table.get(&mini_canvas).unwrap();
+        Ok(match &mini_canvas.window {
+            Some(window) => window.inner_size().height,
+            None => mini_canvas.height,
+        })
     }
 
     fn width(&mut self, mini_canvas: Resource<MiniCanvas>) -> wasmtime::Result<u32> {
-        let _mini_canvas = self.table.get(&mini_canvas).unwrap();
-        Ok(self.window.inner_size().width)
+        let mini_canvas = self.table.get(&mini_canvas).unwrap();
+        Ok(match &mini_canvas.window {
+            Some(window) => window.inner_size().width,
+            None => mini_canvas.width,
+        })
     }
 
-    fn drop(&mut self, _self_: Resource<MiniCanvas>) -> wasmtime::Result<()> {
+    fn drop(&mut self, mini_canvas: Resource<MiniCanvas>) -> wasmtime::Result<()> {
+        self.table.delete(mini_canvas).unwrap();
         Ok(())
     }
 }
@@ -72,6 +169,7 @@ impl crate::wasi::webgpu::mini_canvas::HostMiniCanvas for HostState {
 #[derive(Debug)]
 pub struct ResizeListener {
     receiver: Receiver<HostEvent>,
+    filter: EventFilter,
     data: Mutex<Option<ResizeEvent>>,
 }
 
@@ -80,9 +178,11 @@ impl preview2::Subscribe for ResizeListener {
     async fn ready(&mut self) {
         loop {
             let event = self.receiver.recv().await.unwrap();
-            if let HostEvent::CanvasResizeEvent(event) = event {
-                *self.data.lock().unwrap() = Some(event);
-                return;
+            if let HostEvent::CanvasResizeEvent(window, event) = event {
+                if self.filter.matches(window) {
+                    *self.data.lock().unwrap() = Some(event);
+                    return;
+                }
             }
         }
     }
@@ -102,7 +202,8 @@ impl crate::wasi::webgpu::mini_canvas::HostResizeListener for HostState {
         let pointer_down = self.table.get(&pointer_down).unwrap();
         Ok(pointer_down.data.lock().unwrap().take())
     }
-    fn drop(&mut self, _self_: Resource<ResizeListener>) -> wasmtime::Result<()> {
+    fn drop(&mut self, listener: Resource<ResizeListener>) -> wasmtime::Result<()> {
+        self.table.delete(listener).unwrap();
         Ok(())
     }
 }
@@ -2,7 +2,7 @@ use std::sync::Mutex;
 
 use crate::{
     wasi::webgpu::pointer_events::{PointerEvent, Pollable},
-    HostEvent, HostState,
+    EventFilter, HostEvent, HostState,
 };
 use tokio::sync::broadcast::Receiver;
 use wasmtime::component::Resource;
@@ -13,10 +13,7 @@ impl crate::wasi::webgpu::pointer_events::Host for HostState {
         let receiver = self.sender.subscribe();
         Ok(self
             .table_mut()
-            .push(PointerUpListener {
-                receiver,
-                data: Default::default(),
-            })
+            .push(PointerUpListener::new(receiver, EventFilter::AnyWindow))
             .unwrap())
     }
 
@@ -24,10 +21,7 @@ impl crate::wasi::webgpu::pointer_events::Host for HostState {
         let receiver = self.sender.subscribe();
         Ok(self
             .table_mut()
-            .push(PointerDownListener {
-                receiver,
-                data: Default::default(),
-            })
+            .push(PointerDownListener::new(receiver, EventFilter::AnyWindow))
             .unwrap())
     }
 
@@ -35,10 +29,7 @@ impl crate::wasi::webgpu::pointer_events::Host for HostState {
         let receiver = self.sender.subscribe();
         Ok(self
             .table_mut()
-            .push(PointerMoveListener {
-                receiver,
-                data: Default::default(),
-            })
+            .push(PointerMoveListener::new(receiver, EventFilter::AnyWindow))
             .unwrap())
     }
 }
@@ -57,7 +48,8 @@ impl crate::wasi::webgpu::pointer_events::HostPointerUpListener for HostState {
         let pointer_up = self.table.get(&pointer_up).unwrap();
         Ok(pointer_up.data.lock().unwrap().take())
     }
-    fn drop(&mut self, _self_: Resource<PointerUpListener>) -> wasmtime::Result<()> {
+    fn drop(&mut self, listener: Resource<PointerUpListener>) -> wasmtime::Result<()> {
+        self.table.delete(listener).unwrap();
         Ok(())
     }
 }
@@ -65,17 +57,30 @@ impl crate::wasi::webgpu::pointer_events::HostPointerUpListener for HostState {
 #[derive(Debug)]
 pub struct PointerUpListener {
     receiver: Receiver<HostEvent>,
+    filter: EventFilter,
     data: Mutex<Option<PointerEvent>>,
 }
 
+impl PointerUpListener {
+    pub fn new(receiver: Receiver<HostEvent>, filter: EventFilter) -> Self {
+        PointerUpListener {
+            receiver,
+            filter,
+            data: Default::default(),
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl preview2::Subscribe for PointerUpListener {
     async fn ready(&mut self) {
         loop {
             let event = self.receiver.recv().await.unwrap();
-            if let HostEvent::PointerUpEvent(event) = event {
-                *self.data.lock().unwrap() = Some(event);
-                return;
+            if let HostEvent::PointerUpEvent(window, event) = event {
+                if self.filter.matches(window) {
+                    *self.data.lock().unwrap() = Some(event);
+                    return;
+                }
             }
         }
     }
@@ -95,7 +100,8 @@ impl crate::wasi::webgpu::pointer_events::HostPointerDownListener for HostState
         let pointer_down = self.table.get(&pointer_down).unwrap();
         Ok(pointer_down.data.lock().unwrap().take())
     }
-    fn drop(&mut self, _self_: Resource<PointerDownListener>) -> wasmtime::Result<()> {
+    fn drop(&mut self, listener: Resource<PointerDownListener>) -> wasmtime::Result<()> {
+        self.table.delete(listener).unwrap();
         Ok(())
     }
 }
@@ -103,17 +109,30 @@ impl crate::wasi::webgpu::pointer_events::HostPointerDownListener for HostState
 #[derive(Debug)]
 pub struct PointerDownListener {
     receiver: Receiver<HostEvent>,
+    filter: EventFilter,
     data: Mutex<Option<PointerEvent>>,
 }
 
+impl PointerDownListener {
+    pub fn new(receiver: Receiver<HostEvent>, filter: EventFilter) -> Self {
+        PointerDownListener {
+            receiver,
+            filter,
+            data: Default::default(),
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl preview2::Subscribe for PointerDownListener {
     async fn ready(&mut self) {
         loop {
             let event = self.receiver.recv().await.unwrap();
-            if let HostEvent::PointerDownEvent(event) = event {
-                *self.data.lock().unwrap() = Some(event);
-                return;
+            if let HostEvent::PointerDownEvent(window, event) = event {
+                if self.filter.matches(window) {
+                    *self.data.lock().unwrap() = Some(event);
+                    return;
+                }
             }
         }
     }
@@ -133,7 +152,8 @@ impl crate::wasi::webgpu::pointer_events::HostPointerMoveListener for HostState
         let pointer_move = self.table.get(&pointer_move).unwrap();
         Ok(pointer_move.data.lock().unwrap().take())
     }
-    fn drop(&mut self, _self_: Resource<PointerMoveListener>) -> wasmtime::Result<()> {
+    fn drop(&mut self, listener: Resource<PointerMoveListener>) -> wasmtime::Result<()> {
+        self.table.delete(listener).unwrap();
         Ok(())
     }
 }
@@ -141,17 +161,30 @@ impl crate::wasi::webgpu::pointer_events::HostPointerMoveListener for HostState
 #[derive(Debug)]
 pub struct PointerMoveListener {
     receiver: Receiver<HostEvent>,
+    filter: EventFilter,
     data: Mutex<Option<PointerEvent>>,
 }
 
+impl PointerMoveListener {
+    pub fn new(receiver: Receiver<HostEvent>, filter: EventFilter) -> Self {
+        PointerMoveListener {
+            receiver,
+            filter,
+            data: Default::default(),
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl preview2::Subscribe for PointerMoveListener {
     async fn ready(&mut self) {
         loop {
             let event = self.receiver.recv().await.unwrap();
-            if let HostEvent::PointerMoveEvent(event) = event {
-                *self.data.lock().unwrap() = Some(event);
-                return;
+            if let HostEvent::PointerMoveEvent(window, event) = event {
+                if self.filter.matches(window) {
+                    *self.data.lock().unwrap() = Some(event);
+                    return;
+                }
             }
         }
     }
@@ -1,81 +1,99 @@
-use crate::wasi::webgpu::webgpu;
+use crate::wasi::webgpu::{graphics_context, webgpu};
 
 impl From<wgpu_types::TextureFormat> for webgpu::GpuTextureFormat {
     fn from(value: wgpu_types::TextureFormat) -> Self {
         match value {
             wgpu_types::TextureFormat::Bgra8UnormSrgb => webgpu::GpuTextureFormat::Bgra8unormSrgb,
-            wgpu_types::TextureFormat::R8Unorm => todo!(),
-            wgpu_types::TextureFormat::R8Snorm => todo!(),
-            wgpu_types::TextureFormat::R8Uint => todo!(),
-            wgpu_types::TextureFormat::R8Sint => todo!(),
-            wgpu_types::TextureFormat::R16Uint => todo!(),
-            wgpu_types::TextureFormat::R16Sint => todo!(),
+            wgpu_types::TextureFormat::R8Unorm => webgpu::GpuTextureFormat::R8unorm,
+            wgpu_types::TextureFormat::R8Snorm => webgpu::GpuTextureFormat::R8snorm,
+            wgpu_types::TextureFormat::R8Uint => webgpu::GpuTextureFormat::R8uint,
+            wgpu_types::TextureFormat::R8Sint => webgpu::GpuTextureFormat::R8sint,
+            wgpu_types::TextureFormat::R16Uint => webgpu::GpuTextureFormat::R16uint,
+            wgpu_types::TextureFormat::R16Sint => webgpu::GpuTextureFormat::R16sint,
             wgpu_types::TextureFormat::R16Unorm => todo!(),
             wgpu_types::TextureFormat::R16Snorm => todo!(),
-            wgpu_types::TextureFormat::R16Float => todo!(),
-            wgpu_types::TextureFormat::Rg8Unorm => todo!(),
-            wgpu_types::TextureFormat::Rg8Snorm => todo!(),
-            wgpu_types::TextureFormat::Rg8Uint => todo!(),
-            wgpu_types::TextureFormat::Rg8Sint => todo!(),
-            wgpu_types::TextureFormat::R32Uint => todo!(),
-            wgpu_types::TextureFormat::R32Sint => todo!(),
-            wgpu_types::TextureFormat::R32Float => todo!(),
-            wgpu_types::TextureFormat::Rg16Uint => todo!(),
-            wgpu_types::TextureFormat::Rg16Sint => todo!(),
+            wgpu_types::TextureFormat::R16Float => webgpu::GpuTextureFormat::R16float,
+            wgpu_types::TextureFormat::Rg8Unorm => webgpu::GpuTextureFormat::Rg8unorm,
+            wgpu_types::TextureFormat::Rg8Snorm => webgpu::GpuTextureFormat::Rg8snorm,
+            wgpu_types::TextureFormat::Rg8Uint => webgpu::GpuTextureFormat::Rg8uint,
+            wgpu_types::TextureFormat::Rg8Sint => webgpu::GpuTextureFormat::Rg8sint,
+            wgpu_types::TextureFormat::R32Uint => webgpu::GpuTextureFormat::R32uint,
+            wgpu_types::TextureFormat::R32Sint => webgpu::GpuTextureFormat::R32sint,
+            wgpu_types::TextureFormat::R32Float => webgpu::GpuTextureFormat::R32float,
+            wgpu_types::TextureFormat::Rg16Uint => webgpu::GpuTextureFormat::Rg16uint,
+            wgpu_types::TextureFormat::Rg16Sint => webgpu::GpuTextureFormat::Rg16sint,
             wgpu_types::TextureFormat::Rg16Unorm => todo!(),
             wgpu_types::TextureFormat::Rg16Snorm => todo!(),
-            wgpu_types::TextureFormat::Rg16Float => todo!(),
-            wgpu_types::TextureFormat::Rgba8Unorm => todo!(),
-            wgpu_types::TextureFormat::Rgba8UnormSrgb => todo!(),
-            wgpu_types::TextureFormat::Rgba8Snorm => todo!(),
-            wgpu_types::TextureFormat::Rgba8Uint => todo!(),
-            wgpu_types::TextureFormat::Rgba8Sint => todo!(),
-            wgpu_types::TextureFormat::Bgra8Unorm => todo!(),
-            wgpu_types::TextureFormat::Rgb9e5Ufloat => todo!(),
-            wgpu_types::TextureFormat::Rgb10a2Uint => todo!(),
-            wgpu_types::TextureFormat::Rgb10a2Unorm => todo!(),
-            wgpu_types::TextureFormat::Rg11b10Float => todo!(),
-            wgpu_types::TextureFormat::Rg32Uint => todo!(),
-            wgpu_types::TextureFormat::Rg32Sint => todo!(),
-            wgpu_types::TextureFormat::Rg32Float => todo!(),
-            wgpu_types::TextureFormat::Rgba16Uint => todo!(),
-            wgpu_types::TextureFormat::Rgba16Sint => todo!(),
+            wgpu_types::TextureFormat::Rg16Float => webgpu::GpuTextureFormat::Rg16float,
+            wgpu_types::TextureFormat::Rgba8Unorm => webgpu::GpuTextureFormat::Rgba8unorm,
+            wgpu_types::TextureFormat::Rgba8UnormSrgb => webgpu::GpuTextureFormat::Rgba8unormSrgb,
+            wgpu_types::TextureFormat::Rgba8Snorm => webgpu::GpuTextureFormat::Rgba8snorm,
+            wgpu_types::TextureFormat::Rgba8Uint => webgpu::GpuTextureFormat::Rgba8uint,
+            wgpu_types::TextureFormat::Rgba8Sint => webgpu::GpuTextureFormat::Rgba8sint,
+            wgpu_types::TextureFormat::Bgra8Unorm => webgpu::GpuTextureFormat::Bgra8unorm,
+            wgpu_types::TextureFormat::Rgb9e5Ufloat => webgpu::GpuTextureFormat::Rgb9e5ufloat,
+            wgpu_types::TextureFormat::Rgb10a2Uint => webgpu::GpuTextureFormat::Rgb10a2uint,
+            wgpu_types::TextureFormat::Rgb10a2Unorm => webgpu::GpuTextureFormat::Rgb10a2unorm,
+            wgpu_types::TextureFormat::Rg11b10Float => webgpu::GpuTextureFormat::Rg11b10ufloat,
+            wgpu_types::TextureFormat::Rg32Uint => webgpu::GpuTextureFormat::Rg32uint,
+            wgpu_types::TextureFormat::Rg32Sint => webgpu::GpuTextureFormat::Rg32sint,
+            wgpu_types::TextureFormat::Rg32Float => webgpu::GpuTextureFormat::Rg32float,
+            wgpu_types::TextureFormat::Rgba16Uint => webgpu::GpuTextureFormat::Rgba16uint,
+            wgpu_types::TextureFormat::Rgba16Sint => webgpu::GpuTextureFormat::Rgba16sint,
             wgpu_types::TextureFormat::Rgba16Unorm => todo!(),
             wgpu_types::TextureFormat::Rgba16Snorm => todo!(),
-            wgpu_types::TextureFormat::Rgba16Float => todo!(),
-            wgpu_types::TextureFormat::Rgba32Uint => todo!(),
-            wgpu_types::TextureFormat::Rgba32Sint => todo!(),
-            wgpu_types::TextureFormat::Rgba32Float => todo!(),
-            wgpu_types::TextureFormat::Stencil8 => todo!(),
-            wgpu_types::TextureFormat::Depth16Unorm => todo!(),
-            wgpu_types::TextureFormat::Depth24Plus => todo!(),
-            wgpu_types::TextureFormat::Depth24PlusStencil8 => todo!(),
-            wgpu_types::TextureFormat::Depth32Float => todo!(),
-            wgpu_types::TextureFormat::Depth32FloatStencil8 => todo!(),
-            wgpu_types::TextureFormat::Bc1RgbaUnorm => todo!(),
-            wgpu_types::TextureFormat::Bc1RgbaUnormSrgb => todo!(),
-            wgpu_types::TextureFormat::Bc2RgbaUnorm => todo!(),
-            wgpu_types::TextureFormat::Bc2RgbaUnormSrgb => todo!(),
-            wgpu_types::TextureFormat::Bc3RgbaUnorm => todo!(),
-            wgpu_types::TextureFormat::Bc3RgbaUnormSrgb => todo!(),
-            wgpu_types::TextureFormat::Bc4RUnorm => todo!(),
-            wgpu_types::TextureFormat::Bc4RSnorm => todo!(),
-            wgpu_types::TextureFormat::Bc5RgUnorm => todo!(),
-            wgpu_types::TextureFormat::Bc5RgSnorm => todo!(),
-            wgpu_types::TextureFormat::Bc6hRgbUfloat => todo!(),
-            wgpu_types::TextureFormat::Bc6hRgbFloat => todo!(),
-            wgpu_types::TextureFormat::Bc7RgbaUnorm => todo!(),
-            wgpu_types::TextureFormat::Bc7RgbaUnormSrgb => todo!(),
-            wgpu_types::TextureFormat::Etc2Rgb8Unorm => todo!(),
-            wgpu_types::TextureFormat::Etc2Rgb8UnormSrgb => todo!(),
-            wgpu_types::TextureFormat::Etc2Rgb8A1Unorm => todo!(),
-            wgpu_types::TextureFormat::Etc2Rgb8A1UnormSrgb => todo!(),
-            wgpu_types::TextureFormat::Etc2Rgba8Unorm => todo!(),
-            wgpu_types::TextureFormat::Etc2Rgba8UnormSrgb => todo!(),
-            wgpu_types::TextureFormat::EacR11Unorm => todo!(),
-            wgpu_types::TextureFormat::EacR11Snorm => todo!(),
-            wgpu_types::TextureFormat::EacRg11Unorm => todo!(),
-            wgpu_types::TextureFormat::EacRg11Snorm => todo!(),
+            wgpu_types::TextureFormat::Rgba16Float => webgpu::GpuTextureFormat::Rgba16float,
+            wgpu_types::TextureFormat::Rgba32Uint => webgpu::GpuTextureFormat::Rgba32uint,
+            wgpu_types::TextureFormat::Rgba32Sint => webgpu::GpuTextureFormat::Rgba32sint,
+            wgpu_types::TextureFormat::Rgba32Float => webgpu::GpuTextureFormat::Rgba32float,
+            wgpu_types::TextureFormat::Stencil8 => webgpu::GpuTextureFormat::Stencil8,
+            wgpu_types::TextureFormat::Depth16Unorm => webgpu::GpuTextureFormat::Depth16unorm,
+            wgpu_types::TextureFormat::Depth24Plus => webgpu::GpuTextureFormat::Depth24plus,
+            wgpu_types::TextureFormat::Depth24PlusStencil8 => {
+                webgpu::GpuTextureFormat::Depth24plusStencil8
+            }
+            wgpu_types::TextureFormat::Depth32Float => webgpu::GpuTextureFormat::Depth32float,
+            wgpu_types::TextureFormat::Depth32FloatStencil8 => {
+                webgpu::GpuTextureFormat::Depth32floatStencil8
+            }
+            wgpu_types::TextureFormat::Bc1RgbaUnorm => webgpu::GpuTextureFormat::Bc1RgbaUnorm,
+            wgpu_types::TextureFormat::Bc1RgbaUnormSrgb => {
+                webgpu::GpuTextureFormat::Bc1RgbaUnormSrgb
+            }
+            wgpu_types::TextureFormat::Bc2RgbaUnorm => webgpu::GpuTextureFormat::Bc2RgbaUnorm,
+            wgpu_types::TextureFormat::Bc2RgbaUnormSrgb => {
+                webgpu::GpuTextureFormat::Bc2RgbaUnormSrgb
+            }
+            wgpu_types::TextureFormat::Bc3RgbaUnorm => webgpu::GpuTextureFormat::Bc3RgbaUnorm,
+            wgpu_types::TextureFormat::Bc3RgbaUnormSrgb => {
+                webgpu::GpuTextureFormat::Bc3RgbaUnormSrgb
+            }
+            wgpu_types::TextureFormat::Bc4RUnorm => webgpu::GpuTextureFormat::Bc4RUnorm,
+            wgpu_types::TextureFormat::Bc4RSnorm => webgpu::GpuTextureFormat::Bc4RSnorm,
+            wgpu_types::TextureFormat::Bc5RgUnorm => webgpu::GpuTextureFormat::Bc5RgUnorm,
+            wgpu_types::TextureFormat::Bc5RgSnorm => webgpu::GpuTextureFormat::Bc5RgSnorm,
+            wgpu_types::TextureFormat::Bc6hRgbUfloat => webgpu::GpuTextureFormat::Bc6hRgbUfloat,
+            wgpu_types::TextureFormat::Bc6hRgbFloat => webgpu::GpuTextureFormat::Bc6hRgbFloat,
+            wgpu_types::TextureFormat::Bc7RgbaUnorm => webgpu::GpuTextureFormat::Bc7RgbaUnorm,
+            wgpu_types::TextureFormat::Bc7RgbaUnormSrgb => {
+                webgpu::GpuTextureFormat::Bc7RgbaUnormSrgb
+            }
+            wgpu_types::TextureFormat::Etc2Rgb8Unorm => webgpu::GpuTextureFormat::Etc2Rgb8unorm,
+            wgpu_types::TextureFormat::Etc2Rgb8UnormSrgb => {
+                webgpu::GpuTextureFormat::Etc2Rgb8unormSrgb
+            }
+            wgpu_types::TextureFormat::Etc2Rgb8A1Unorm => webgpu::GpuTextureFormat::Etc2Rgb8a1unorm,
+            wgpu_types::TextureFormat::Etc2Rgb8A1UnormSrgb => {
+                webgpu::GpuTextureFormat::Etc2Rgb8a1unormSrgb
+            }
+            wgpu_types::TextureFormat::Etc2Rgba8Unorm => webgpu::GpuTextureFormat::Etc2Rgba8unorm,
+            wgpu_types::TextureFormat::Etc2Rgba8UnormSrgb => {
+                webgpu::GpuTextureFormat::Etc2Rgba8unormSrgb
+            }
+            wgpu_types::TextureFormat::EacR11Unorm => webgpu::GpuTextureFormat::EacR11unorm,
+            wgpu_types::TextureFormat::EacR11Snorm => webgpu::GpuTextureFormat::EacR11snorm,
+            wgpu_types::TextureFormat::EacRg11Unorm => webgpu::GpuTextureFormat::EacRg11unorm,
+            wgpu_types::TextureFormat::EacRg11Snorm => webgpu::GpuTextureFormat::EacRg11snorm,
             wgpu_types::TextureFormat::Astc { .. } => todo!(),
         }
     }
@@ -233,6 +251,16 @@ impl From<wgpu_types::PrimitiveTopology> for webgpu::GpuPrimitiveTopology {
     }
 }
 
+impl From<wgpu_types::TextureDimension> for webgpu::GpuTextureDimension {
+    fn from(value: wgpu_types::TextureDimension) -> Self {
+        match value {
+            wgpu_types::TextureDimension::D1 => webgpu::GpuTextureDimension::OneD,
+            wgpu_types::TextureDimension::D2 => webgpu::GpuTextureDimension::TwoD,
+            wgpu_types::TextureDimension::D3 => webgpu::GpuTextureDimension::ThreeD,
+        }
+    }
+}
+
 impl From<webgpu::GpuTextureDimension> for wgpu_types::TextureDimension {
     fn from(value: webgpu::GpuTextureDimension) -> Self {
         match value {
@@ -512,6 +540,26 @@ impl From<webgpu::GpuStencilOperation> for wgpu_types::StencilOperation {
     }
 }
 
+impl From<webgpu::GpuPowerPreference> for wgpu_types::PowerPreference {
+    fn from(value: webgpu::GpuPowerPreference) -> Self {
+        match value {
+            webgpu::GpuPowerPreference::LowPower => wgpu_types::PowerPreference::LowPower,
+            webgpu::GpuPowerPreference::HighPerformance => {
+                wgpu_types::PowerPreference::HighPerformance
+            }
+        }
+    }
+}
+
+impl From<webgpu::GpuQueryType> for wgpu_types::QueryType {
+    fn from(value: webgpu::GpuQueryType) -> Self {
+        match value {
+            webgpu::GpuQueryType::Occlusion => wgpu_types::QueryType::Occlusion,
+            webgpu::GpuQueryType::Timestamp => wgpu_types::QueryType::Timestamp,
+        }
+    }
+}
+
 impl From<webgpu::GpuTextureAspect> for wgpu_types::TextureAspect {
     fn from(value: webgpu::GpuTextureAspect) -> Self {
         match value {
@@ -521,3 +569,50 @@ impl From<webgpu::GpuTextureAspect> for wgpu_types::TextureAspect {
         }
     }
 }
+
+impl From<graphics_context::ContextFormat> for wgpu_types::TextureFormat {
+    fn from(value: graphics_context::ContextFormat) -> Self {
+        match value {
+            graphics_context::ContextFormat::Bgra8unorm => wgpu_types::TextureFormat::Bgra8Unorm,
+            graphics_context::ContextFormat::Bgra8unormSrgb => {
+                wgpu_types::TextureFormat::Bgra8UnormSrgb
+            }
+            graphics_context::ContextFormat::Rgba8unorm => wgpu_types::TextureFormat::Rgba8Unorm,
+            graphics_context::ContextFormat::Rgba8unormSrgb => {
+                wgpu_types::TextureFormat::Rgba8UnormSrgb
+            }
+            graphics_context::ContextFormat::Rgba16float => wgpu_types::TextureFormat::Rgba16Float,
+        }
+    }
+}
+
+impl From<graphics_context::PresentMode> for wgpu_types::PresentMode {
+    fn from(value: graphics_context::PresentMode) -> Self {
+        match value {
+            graphics_context::PresentMode::AutoVsync => wgpu_types::PresentMode::AutoVsync,
+            graphics_context::PresentMode::AutoNoVsync => wgpu_types::PresentMode::AutoNoVsync,
+            graphics_context::PresentMode::Fifo => wgpu_types::PresentMode::Fifo,
+            graphics_context::PresentMode::FifoRelaxed => wgpu_types::PresentMode::FifoRelaxed,
+            graphics_context::PresentMode::Immediate => wgpu_types::PresentMode::Immediate,
+            graphics_context::PresentMode::Mailbox => wgpu_types::PresentMode::Mailbox,
+        }
+    }
+}
+
+impl From<graphics_context::CompositeAlphaMode> for wgpu_types::CompositeAlphaMode {
+    fn from(value: graphics_context::CompositeAlphaMode) -> Self {
+        match value {
+            graphics_context::CompositeAlphaMode::Auto => wgpu_types::CompositeAlphaMode::Auto,
+            graphics_context::CompositeAlphaMode::Opaque => wgpu_types::CompositeAlphaMode::Opaque,
+            graphics_context::CompositeAlphaMode::PreMultiplied => {
+                wgpu_types::CompositeAlphaMode::PreMultiplied
+            }
+            graphics_context::CompositeAlphaMode::PostMultiplied => {
+                wgpu_types::CompositeAlphaMode::PostMultiplied
+            }
+            graphics_context::CompositeAlphaMode::Inherit => {
+                wgpu_types::CompositeAlphaMode::Inherit
+            }
+        }
+    }
+}
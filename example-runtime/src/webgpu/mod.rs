@@ -2,18 +2,37 @@
 // - Remove all calls to `Default::default()`. Instead, manually set them, and link to the spec stating what defaults should be used.
 // - Implement all todos.
 // - Remove all unwraps.
-// - Implement all the drop handlers.
 
+use anyhow::Context;
 use core::slice;
+use futures::FutureExt;
 use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
 use std::borrow::Cow;
+use std::ffi::CString;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast::{error::RecvError, Receiver, Sender};
 use wasmtime::component::Resource;
+use wasmtime_wasi::preview2::{self, WasiView};
+use winit::window::Window;
 
 use crate::wasi::webgpu::webgpu;
+use crate::wasi::webgpu::graphics_context::{self, ConfigureContextDesc};
 use crate::graphics_context::{GraphicsContext, GraphicsContextBuffer, GraphicsContextKind};
-use crate::HostState;
+use crate::{HostEvent, HostState};
 
-use self::to_core_conversions::ToCore;
+use self::to_core_conversions::{
+    image_copy_to_core, texture_usages_to_core, ToCore, TryToCore, ValidationError,
+};
+
+// https://www.w3.org/TR/webgpu/#dom-gpu-getpreferredcanvasformat
+// used when there's no adapter to ask.
+const FALLBACK_CANVAS_FORMAT: wgpu_types::TextureFormat = wgpu_types::TextureFormat::Bgra8Unorm;
+
+// https://www.w3.org/TR/WGSL/#language-extensions-sec
+// naga 0.14, which wgpu-core 0.18 uses, doesn't implement any of the language extensions yet. Add them here when updating naga.
+const WGSL_LANGUAGE_FEATURES: &[&str] = &[];
 
 // ToCore trait used for resources, records, and variants.
 // Into trait used for enums, since they never need table access.
@@ -36,16 +55,1140 @@ impl RemoteBuffer {
 unsafe impl Send for RemoteBuffer {}
 unsafe impl Sync for RemoteBuffer {}
 
+// https://www.w3.org/TR/webgpu/#dom-gpubuffer-getmappedrange
+// the buffer detaches its ranges when it's unmapped, since the memory they point to is gone after that.
+#[derive(Clone, Default)]
+pub struct MappedRange(Arc<Mutex<Option<RemoteBuffer>>>);
+
+impl MappedRange {
+    fn new(remote_buffer: RemoteBuffer) -> Self {
+        MappedRange(Arc::new(Mutex::new(Some(remote_buffer))))
+    }
+}
+
+// wgpu 0.18 doesn't have these limits yet, so we report the webgpu defaults.
+// https://www.w3.org/TR/webgpu/#limits
+const MAX_BIND_GROUPS_PLUS_VERTEX_BUFFERS: u32 = 24;
+const MAX_INTER_STAGE_SHADER_VARIABLES: u32 = 16;
+const MAX_COLOR_ATTACHMENTS: u32 = 8;
+const MAX_COLOR_ATTACHMENT_BYTES_PER_SAMPLE: u32 = 32;
+
+// https://www.w3.org/TR/webgpu/#gpuerror
+#[derive(Clone, Debug)]
+pub struct Error {
+    kind: webgpu::GpuErrorFilter,
+    message: String,
+}
+
+impl Error {
+    fn from_core(error: &(dyn std::error::Error + 'static)) -> Self {
+        let kind = match find_device_error(error) {
+            Some(wgpu_core::device::DeviceError::OutOfMemory) => {
+                webgpu::GpuErrorFilter::OutOfMemory
+            }
+            Some(wgpu_core::device::DeviceError::ResourceCreationFailed) => {
+                webgpu::GpuErrorFilter::Internal
+            }
+            _ if is_internal_error(error) => webgpu::GpuErrorFilter::Internal,
+            _ => webgpu::GpuErrorFilter::Validation,
+        };
+        let mut message = error.to_string();
+        let mut next = error.source();
+        while let Some(error) = next {
+            message.push_str(": ");
+            message.push_str(&error.to_string());
+            next = error.source();
+        }
+        Error { kind, message }
+    }
+}
+
+// wgpu-core takes debug labels as C strings, which can't hold NULs, so those are left out of the label.
+fn debug_label(label: String) -> CString {
+    let mut bytes = label.into_bytes();
+    bytes.retain(|&b| b != 0);
+    // SAFETY: the NULs were just removed.
+    unsafe { CString::from_vec_unchecked(bytes) }
+}
+
+// https://www.w3.org/TR/webgpu/#dom-gpudebugcommandsmixin-popdebuggroup
+fn pop_empty_debug_group() -> ValidationError {
+    ValidationError("pop-debug-group called without a matching push-debug-group".to_string())
+}
+
+// https://www.w3.org/TR/webgpu/#dom-gpucommandencoder-finish
+fn unbalanced_debug_groups(depth: u32) -> ValidationError {
+    ValidationError(format!(
+        "{depth} debug group(s) were pushed but never popped"
+    ))
+}
+
+// wgpu-core takes a missing size to mean the rest of the buffer, so it has no way to bind an empty range.
+fn buffer_binding_size(size: u64) -> Result<wgpu_types::BufferSize, ValidationError> {
+    wgpu_types::BufferSize::new(size)
+        .ok_or_else(|| ValidationError("binding an empty buffer range isn't supported".to_string()))
+}
+
+// https://www.w3.org/TR/webgpu/#dom-gpubuffer-mapasync
+fn map_async_args(
+    buffer_size: u64,
+    mode: webgpu::GpuMapModeFlags,
+    offset: Option<webgpu::GpuSize64>,
+    size: Option<webgpu::GpuSize64>,
+) -> Result<(wgpu_core::device::HostMap, std::ops::Range<u64>), ValidationError> {
+    // https://www.w3.org/TR/webgpu/#dom-gpumapmode-read
+    let host = match mode {
+        1 => wgpu_core::device::HostMap::Read,
+        2 => wgpu_core::device::HostMap::Write,
+        _ => {
+            return Err(ValidationError(format!(
+                "map mode has to be either read or write, got {mode}"
+            )))
+        }
+    };
+    let offset = offset.unwrap_or(0);
+    let out_of_bounds = || {
+        ValidationError(format!(
+            "map range is out of bounds for a buffer of {buffer_size} bytes"
+        ))
+    };
+    let size = match size {
+        Some(size) => size,
+        None => buffer_size.checked_sub(offset).ok_or_else(out_of_bounds)?,
+    };
+    let end = offset.checked_add(size).ok_or_else(out_of_bounds)?;
+    Ok((host, offset..end))
+}
+
+// https://www.w3.org/TR/webgpu/#gpuinternalerror
+// Shaders that pass validation but that the backend still fails to compile.
+fn is_internal_error(error: &(dyn std::error::Error + 'static)) -> bool {
+    use wgpu_core::pipeline::{CreateComputePipelineError, CreateRenderPipelineError};
+    let mut next = Some(error);
+    while let Some(error) = next {
+        if let Some(CreateComputePipelineError::Internal(_)) = error.downcast_ref() {
+            return true;
+        }
+        if let Some(CreateRenderPipelineError::Internal { .. }) = error.downcast_ref() {
+            return true;
+        }
+        next = error.source();
+    }
+    false
+}
+
+// wgpu-core nests errors, so the device error can be anywhere in the chain.
+fn find_device_error<'a>(
+    error: &'a (dyn std::error::Error + 'static),
+) -> Option<&'a wgpu_core::device::DeviceError> {
+    let mut next = Some(error);
+    while let Some(error) = next {
+        if let Some(device_error) = error.downcast_ref::<wgpu_core::device::DeviceError>() {
+            return Some(device_error);
+        }
+        next = error.source();
+    }
+    None
+}
+
+struct ErrorScope {
+    filter: webgpu::GpuErrorFilter,
+    // only the first error in a scope is kept.
+    error: Option<Error>,
+}
+
+// Every resource that can produce an error holds on to its device's sink, since the table can't give us a child's parent.
+#[derive(Clone)]
+pub struct ErrorSink {
+    device: wgpu_core::id::DeviceId,
+    scopes: Arc<Mutex<Vec<ErrorScope>>>,
+    // lives here since any resource can be the first to find out that the device is lost.
+    lost: Arc<Mutex<Option<DeviceLostInfo>>>,
+    // wgpu-core frees a dropped device once nothing uses it anymore, after which its id can't be polled.
+    dropped: Arc<AtomicBool>,
+}
+
+impl ErrorSink {
+    fn new(device: wgpu_core::id::DeviceId) -> Self {
+        ErrorSink {
+            device,
+            scopes: Default::default(),
+            lost: Default::default(),
+            dropped: Default::default(),
+        }
+    }
+
+    // https://www.w3.org/TR/webgpu/#lose-the-device
+    fn lose(&self, reason: webgpu::GpuDeviceLostReason, message: String) {
+        let mut lost = self.lost.lock().unwrap();
+        if lost.is_none() {
+            log::warn!("webgpu device lost: {message}");
+            *lost = Some(DeviceLostInfo { reason, message });
+        }
+    }
+
+    // https://www.w3.org/TR/webgpu/#abstract-opdef-dispatch-error
+    // takes the sender instead of going through HostState, so errors found off the guest's thread can be reported too.
+    fn dispatch(&self, sender: &Sender<HostEvent>, error: impl std::error::Error + 'static) {
+        if let Some(wgpu_core::device::DeviceError::Lost) = find_device_error(&error) {
+            self.lose(webgpu::GpuDeviceLostReason::Unknown, error.to_string());
+        }
+        // lost devices don't generate errors anymore.
+        if self.lost.lock().unwrap().is_some() {
+            return;
+        }
+        let error = Error::from_core(&error);
+        let mut scopes = self.scopes.lock().unwrap();
+        match scopes
+            .iter_mut()
+            .rev()
+            .find(|scope| scope.filter == error.kind)
+        {
+            Some(scope) => {
+                if scope.error.is_none() {
+                    scope.error = Some(error);
+                }
+            }
+            None => {
+                log::warn!("uncaptured webgpu error: {}", error.message);
+                // only fails if nothing is listening.
+                let _ = sender.send(HostEvent::UncapturedGpuError(self.device, error));
+            }
+        }
+    }
+}
+
+// https://www.w3.org/TR/webgpu/#dom-gpu-getpreferredcanvasformat
+// the spec only allows bgra8unorm and rgba8unorm, so pick whichever one the adapter can render to.
+pub(crate) fn preferred_canvas_format(
+    instance: &wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>,
+    force_fallback_adapter: bool,
+) -> wgpu_types::TextureFormat {
+    let options = wgpu_core::instance::RequestAdapterOptions {
+        power_preference: wgpu_types::PowerPreference::None,
+        force_fallback_adapter,
+        compatible_surface: None,
+    };
+    let Ok(adapter) = instance.request_adapter(
+        &options,
+        wgpu_core::instance::AdapterInputs::Mask(wgpu_types::Backends::all(), |_| ()),
+    ) else {
+        return FALLBACK_CANVAS_FORMAT;
+    };
+    let format = [
+        wgpu_types::TextureFormat::Bgra8Unorm,
+        wgpu_types::TextureFormat::Rgba8Unorm,
+    ]
+    .into_iter()
+    .find(|&format| {
+        instance
+            .adapter_get_texture_format_features::<crate::Backend>(adapter, format)
+            .is_ok_and(|features| {
+                features
+                    .allowed_usages
+                    .contains(wgpu_types::TextureUsages::RENDER_ATTACHMENT)
+            })
+    })
+    .unwrap_or(FALLBACK_CANVAS_FORMAT);
+    instance.adapter_drop::<crate::Backend>(adapter);
+    format
+}
+
+impl HostState {
+    // wgpu-core checks the configuration against the surface's capabilities.
+    pub(crate) fn configure_surface(
+        &self,
+        device: &Device,
+        surface: wgpu_core::id::SurfaceId,
+        config: &wgpu_types::SurfaceConfiguration<Vec<wgpu_types::TextureFormat>>,
+    ) {
+        if let Some(e) =
+            self.instance
+                .surface_configure::<crate::Backend>(surface, device.device, config)
+        {
+            self.handle_error(&device.error_sink, e);
+        }
+    }
+
+    // https://www.w3.org/TR/webgpu/#dom-gpucanvascontext-getcurrenttexture
+    // none if the surface has nothing to draw to this frame, the frame is skipped then.
+    pub(crate) fn surface_current_texture(
+        &self,
+        device: &Device,
+        surface: wgpu_core::id::SurfaceId,
+        config: &mut wgpu_types::SurfaceConfiguration<Vec<wgpu_types::TextureFormat>>,
+        window: &Window,
+    ) -> Option<wgpu_core::id::TextureId> {
+        let output = match self
+            .instance
+            .surface_get_current_texture::<crate::Backend>(surface, ())
+        {
+            Ok(output) => output,
+            Err(e) => {
+                self.handle_error(&device.error_sink, e);
+                return None;
+            }
+        };
+        match output.status {
+            wgpu_types::SurfaceStatus::Good | wgpu_types::SurfaceStatus::Suboptimal => {
+                output.texture_id
+            }
+            // the swap chain no longer matches the window, or is gone, and has to be recreated.
+            wgpu_types::SurfaceStatus::Outdated | wgpu_types::SurfaceStatus::Lost => {
+                // the resize event for the window's new size might not have arrived yet.
+                let size = window.inner_size();
+                config.width = size.width.max(1);
+                config.height = size.height.max(1);
+                self.configure_surface(device, surface, config);
+                match self
+                    .instance
+                    .surface_get_current_texture::<crate::Backend>(surface, ())
+                {
+                    Ok(output) if output.texture_id.is_some() => output.texture_id,
+                    Ok(output) => {
+                        log::warn!("skipping a frame, surface is still {:?}", output.status);
+                        None
+                    }
+                    Err(e) => {
+                        self.handle_error(&device.error_sink, e);
+                        None
+                    }
+                }
+            }
+            wgpu_types::SurfaceStatus::Timeout => {
+                log::warn!("skipping a frame, surface timed out");
+                None
+            }
+        }
+    }
+
+    pub(crate) fn present_surface(&self, device: &Device, surface: wgpu_core::id::SurfaceId) {
+        match self.instance.surface_present::<crate::Backend>(surface) {
+            Ok(_) => {}
+            // nothing was acquired when surface_current_texture skipped the frame.
+            Err(wgpu_core::present::SurfaceError::AlreadyAcquired) => {}
+            Err(e) => self.handle_error(&device.error_sink, e),
+        }
+    }
+
+    // wgpu-core 0.18 can't hand out an invalid texture id directly, but always rejects one without size or usage. Its error is dropped, the caller already reported why the texture is invalid.
+    pub(crate) fn create_invalid_texture(
+        &self,
+        device: wgpu_core::id::DeviceId,
+        descriptor: &wgpu_core::resource::TextureDescriptor<'static>,
+    ) -> wgpu_core::id::TextureId {
+        let descriptor = wgpu_types::TextureDescriptor {
+            size: wgpu_types::Extent3d {
+                width: 0,
+                height: 0,
+                depth_or_array_layers: 0,
+            },
+            usage: wgpu_types::TextureUsages::empty(),
+            view_formats: vec![],
+            ..descriptor.clone()
+        };
+        let (texture, _) =
+            self.instance
+                .device_create_texture::<crate::Backend>(device, &descriptor, ());
+        texture
+    }
+
+    fn create_offscreen_texture(
+        &self,
+        device: &Device,
+        descriptor: &wgpu_core::resource::TextureDescriptor<'static>,
+    ) -> wgpu_core::id::TextureId {
+        self.handle_core_result(
+            &device.error_sink,
+            self.instance
+                .device_create_texture::<crate::Backend>(device.device, descriptor, ()),
+        )
+    }
+
+    // https://www.w3.org/TR/webgpu/#dom-gpucanvascontext-configure
+    pub(crate) fn configure_context(
+        &self,
+        kind: GraphicsContextKind,
+        desc: ConfigureContextDesc,
+    ) -> GraphicsContextKind {
+        let (GraphicsContextKind::Webgpu { device, .. }
+        | GraphicsContextKind::WebgpuOffscreen { device, .. }) = &kind
+        else {
+            // frame buffers always show their pixels as they are.
+            return kind;
+        };
+        // wgpu-core doesn't know about color spaces, surfaces are always srgb.
+        if let Some(graphics_context::PredefinedColorSpace::DisplayP3) = desc.color_space {
+            self.handle_error(
+                &device.error_sink,
+                ValidationError("only the srgb color space is supported".to_string()),
+            );
+        }
+
+        let format = desc.format.into();
+        let usage = match desc.usage.map(texture_usages_to_core).transpose() {
+            Ok(usage) => usage.unwrap_or(wgpu_types::TextureUsages::RENDER_ATTACHMENT),
+            Err(e) => {
+                // the context keeps its previous configuration.
+                self.handle_error(&device.error_sink, e);
+                return kind;
+            }
+        };
+        let view_formats = desc
+            .view_formats
+            .map(|view_formats| view_formats.into_iter().map(|f| f.into()).collect())
+            .unwrap_or_default();
+
+        match kind {
+            GraphicsContextKind::Webgpu {
+                surface,
+                device,
+                config,
+                resized,
+                window,
+            } => {
+                let config = wgpu_types::SurfaceConfiguration {
+                    usage,
+                    format,
+                    width: config.width,
+                    height: config.height,
+                    present_mode: desc
+                        .present_mode
+                        .map(|p| p.into())
+                        .unwrap_or(wgpu_types::PresentMode::Fifo),
+                    alpha_mode: desc.alpha_mode.into(),
+                    view_formats,
+                };
+                self.configure_surface(&device, surface, &config);
+                GraphicsContextKind::Webgpu {
+                    surface,
+                    device,
+                    config,
+                    resized,
+                    window,
+                }
+            }
+            GraphicsContextKind::WebgpuOffscreen {
+                texture,
+                device,
+                descriptor,
+            } => {
+                self.instance.texture_drop::<crate::Backend>(texture, false);
+                let descriptor = wgpu_types::TextureDescriptor {
+                    format,
+                    // copy-src so unattended guests can read back what they rendered.
+                    usage: usage | wgpu_types::TextureUsages::COPY_SRC,
+                    view_formats,
+                    ..descriptor
+                };
+                let texture = self.create_offscreen_texture(&device, &descriptor);
+                GraphicsContextKind::WebgpuOffscreen {
+                    texture,
+                    device,
+                    descriptor,
+                }
+            }
+            kind => kind,
+        }
+    }
+
+    fn handle_error(&self, error_sink: &ErrorSink, error: impl std::error::Error + 'static) {
+        error_sink.dispatch(&self.sender, error);
+    }
+
+    // On error, wgpu-core still hands back an id, which is marked invalid. That matches webgpu, so we can return it to the guest as is.
+    fn handle_core_result<I, E>(
+        &self,
+        error_sink: &ErrorSink,
+        (id, error): (wgpu_core::id::Id<I>, Option<E>),
+    ) -> wgpu_core::id::Id<I>
+    where
+        E: std::error::Error + 'static,
+    {
+        if let Some(error) = error {
+            self.handle_error(error_sink, error);
+        }
+        id
+    }
+
+    // https://www.w3.org/TR/webgpu/#dom-gpudevice-createrenderpipeline
+    fn create_pipeline<P: CorePipeline>(
+        &self,
+        device: &Device,
+        descriptor: Result<P::Descriptor, ValidationError>,
+        module: wgpu_core::id::ShaderModuleId,
+        label: Option<String>,
+    ) -> Pipeline<P> {
+        let pipeline = match descriptor {
+            Ok(descriptor) => {
+                let (pipeline, error) = P::create(&self.instance, device.device, &descriptor);
+                if let Some(e) = error {
+                    self.handle_error(&device.error_sink, e);
+                }
+                pipeline
+            }
+            Err(e) => {
+                self.handle_error(&device.error_sink, e);
+                P::create_invalid(&self.instance, device.device, module, label.as_deref())
+            }
+        };
+        Pipeline::new(pipeline, device.error_sink.clone(), label)
+    }
+
+    // https://www.w3.org/TR/webgpu/#dom-gpudevice-createrenderpipelineasync
+    // errors reject the future instead of going to the device's error scopes.
+    fn create_pipeline_async<P: CorePipeline>(
+        &self,
+        device: &Device,
+        descriptor: Result<P::Descriptor, ValidationError>,
+        label: Option<String>,
+    ) -> PipelineFuture<P> {
+        let instance = Arc::clone(&self.instance);
+        let descriptor = match descriptor {
+            Ok(descriptor) => descriptor,
+            Err(e) => {
+                return PipelineFuture {
+                    instance,
+                    task: None,
+                    result: Some(Err(PipelineError::from_core(&e))),
+                };
+            }
+        };
+        let task_instance = Arc::clone(&instance);
+        let error_sink = device.error_sink.clone();
+        let device = device.device;
+        let task = tokio::task::spawn_blocking(move || {
+            let instance = task_instance;
+            match P::create(&instance, device, &descriptor) {
+                (pipeline, None) => Ok(Pipeline::new(pipeline, error_sink, label)),
+                (pipeline, Some(e)) => {
+                    if let Some(wgpu_core::device::DeviceError::Lost) = find_device_error(&e) {
+                        error_sink.lose(webgpu::GpuDeviceLostReason::Unknown, e.to_string());
+                    }
+                    pipeline.release(&instance);
+                    Err(PipelineError::from_core(&e))
+                }
+            }
+        });
+        PipelineFuture {
+            instance,
+            task: Some(task),
+            result: None,
+        }
+    }
+}
+
+pub struct UncapturedErrorEvent {
+    error: Error,
+}
+
+#[derive(Debug)]
+pub struct UncapturedErrorListener {
+    device: wgpu_core::id::DeviceId,
+    receiver: Receiver<HostEvent>,
+    data: Mutex<Option<Error>>,
+}
+
+#[async_trait::async_trait]
+impl preview2::Subscribe for UncapturedErrorListener {
+    async fn ready(&mut self) {
+        loop {
+            let event = match self.receiver.recv().await {
+                Ok(event) => event,
+                // the channel is shared with every other host event, so a listener that isn't polled often falls behind. It only misses whatever was dropped.
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => futures::future::pending().await,
+            };
+            if let HostEvent::UncapturedGpuError(device, error) = event {
+                if device == self.device {
+                    *self.data.lock().unwrap() = Some(error);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+// https://www.w3.org/TR/webgpu/#gpudevicelostinfo
+#[derive(Clone, Debug)]
+pub struct DeviceLostInfo {
+    reason: webgpu::GpuDeviceLostReason,
+    message: String,
+}
+
+pub struct DeviceLostFuture {
+    instance: Arc<wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>>,
+    device: wgpu_core::id::DeviceId,
+    error_sink: ErrorSink,
+}
+
+#[async_trait::async_trait]
+impl preview2::Subscribe for DeviceLostFuture {
+    async fn ready(&mut self) {
+        loop {
+            if self.error_sink.lost.lock().unwrap().is_some() {
+                return;
+            }
+            // a dropped device can't be lost anymore, so this never resolves.
+            if self.error_sink.dropped.load(Ordering::Relaxed) {
+                futures::future::pending::<()>().await;
+            }
+            // wgpu-core doesn't tell us when a device is lost, but polling a lost device fails.
+            if let Err(e) = self
+                .instance
+                .device_poll::<crate::Backend>(self.device, wgpu_types::Maintain::Poll)
+            {
+                if let Some(wgpu_core::device::DeviceError::Lost) = find_device_error(&e) {
+                    self.error_sink
+                        .lose(webgpu::GpuDeviceLostReason::Unknown, e.to_string());
+                    return;
+                }
+            }
+            // device loss is rare, no need to check as often as for buffer maps.
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+}
+
+// https://www.w3.org/TR/webgpu/#gpuwgsllanguagefeatures
+// the features only depend on the naga version, so there's nothing to hold on to.
+pub struct WgslLanguageFeatures;
+
+// https://www.w3.org/TR/webgpu/#gpupipelineerror
+#[derive(Debug)]
+pub struct PipelineError {
+    reason: webgpu::GpuPipelineErrorReason,
+    message: String,
+}
+
+impl PipelineError {
+    fn from_core(error: &(dyn std::error::Error + 'static)) -> Self {
+        let error = Error::from_core(error);
+        let reason = match error.kind {
+            webgpu::GpuErrorFilter::Validation => webgpu::GpuPipelineErrorReason::Validation,
+            webgpu::GpuErrorFilter::OutOfMemory | webgpu::GpuErrorFilter::Internal => {
+                webgpu::GpuPipelineErrorReason::Internal
+            }
+        };
+        PipelineError {
+            reason,
+            message: error.message,
+        }
+    }
+
+    // the worker thread creating the pipeline panicked, or was cancelled when the runtime shut down.
+    fn from_join_error(error: tokio::task::JoinError) -> Self {
+        PipelineError {
+            reason: webgpu::GpuPipelineErrorReason::Internal,
+            message: format!("pipeline creation didn't finish: {error}"),
+        }
+    }
+}
+
+// What differs between creating render and compute pipelines, so the sync and async paths can share the rest.
+pub trait CorePipeline: Copy + Send + Sync + 'static {
+    type Descriptor: Send + 'static;
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    fn create(
+        instance: &wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>,
+        device: wgpu_core::id::DeviceId,
+        descriptor: &Self::Descriptor,
+    ) -> (Self, Option<Self::Error>);
+
+    // wgpu-core 0.18 can't make an invalid pipeline directly, so it's asked for one with an entry point that can't exist. Its error is dropped, since the caller already reported why the pipeline is invalid.
+    fn create_invalid(
+        instance: &wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>,
+        device: wgpu_core::id::DeviceId,
+        module: wgpu_core::id::ShaderModuleId,
+        label: Option<&str>,
+    ) -> Self;
+
+    fn release(
+        self,
+        instance: &wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>,
+    );
+}
+
+// wgpu-core only makes the bind group layouts itself when it's given ids for them.
+fn implicit_pipeline_ids(
+    layout: Option<wgpu_core::id::PipelineLayoutId>,
+) -> Option<
+    wgpu_core::device::ImplicitPipelineIds<'static, wgpu_core::identity::IdentityManagerFactory>,
+> {
+    match layout {
+        Some(_) => None,
+        None => Some(wgpu_core::device::ImplicitPipelineIds {
+            root_id: (),
+            group_ids: &[(); wgpu_core::MAX_BIND_GROUPS],
+        }),
+    }
+}
+
+impl CorePipeline for wgpu_core::id::RenderPipelineId {
+    type Descriptor = wgpu_core::pipeline::RenderPipelineDescriptor<'static>;
+    type Error = wgpu_core::pipeline::CreateRenderPipelineError;
+
+    fn create(
+        instance: &wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>,
+        device: wgpu_core::id::DeviceId,
+        descriptor: &Self::Descriptor,
+    ) -> (Self, Option<Self::Error>) {
+        instance.device_create_render_pipeline::<crate::Backend>(
+            device,
+            descriptor,
+            (),
+            implicit_pipeline_ids(descriptor.layout),
+        )
+    }
+
+    fn create_invalid(
+        instance: &wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>,
+        device: wgpu_core::id::DeviceId,
+        module: wgpu_core::id::ShaderModuleId,
+        label: Option<&str>,
+    ) -> Self {
+        let descriptor = wgpu_core::pipeline::RenderPipelineDescriptor {
+            label: label.map(Cow::Borrowed),
+            layout: None,
+            vertex: wgpu_core::pipeline::VertexState {
+                stage: wgpu_core::pipeline::ProgrammableStageDescriptor {
+                    module,
+                    entry_point: "".into(),
+                },
+                buffers: Cow::Borrowed(&[]),
+            },
+            primitive: Default::default(),
+            depth_stencil: None,
+            multisample: Default::default(),
+            fragment: None,
+            multiview: None,
+        };
+        let (render_pipeline, _) =
+            instance.device_create_render_pipeline::<crate::Backend>(device, &descriptor, (), None);
+        render_pipeline
+    }
+
+    fn release(
+        self,
+        instance: &wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>,
+    ) {
+        instance.render_pipeline_drop::<crate::Backend>(self);
+    }
+}
+
+impl CorePipeline for wgpu_core::id::ComputePipelineId {
+    type Descriptor = wgpu_core::pipeline::ComputePipelineDescriptor<'static>;
+    type Error = wgpu_core::pipeline::CreateComputePipelineError;
+
+    fn create(
+        instance: &wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>,
+        device: wgpu_core::id::DeviceId,
+        descriptor: &Self::Descriptor,
+    ) -> (Self, Option<Self::Error>) {
+        instance.device_create_compute_pipeline::<crate::Backend>(
+            device,
+            descriptor,
+            (),
+            implicit_pipeline_ids(descriptor.layout),
+        )
+    }
+
+    fn create_invalid(
+        instance: &wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>,
+        device: wgpu_core::id::DeviceId,
+        module: wgpu_core::id::ShaderModuleId,
+        label: Option<&str>,
+    ) -> Self {
+        let descriptor = wgpu_core::pipeline::ComputePipelineDescriptor {
+            label: label.map(Cow::Borrowed),
+            layout: None,
+            stage: wgpu_core::pipeline::ProgrammableStageDescriptor {
+                module,
+                entry_point: "".into(),
+            },
+        };
+        let (compute_pipeline, _) = instance.device_create_compute_pipeline::<crate::Backend>(
+            device,
+            &descriptor,
+            (),
+            None,
+        );
+        compute_pipeline
+    }
+
+    fn release(
+        self,
+        instance: &wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>,
+    ) {
+        instance.compute_pipeline_drop::<crate::Backend>(self);
+    }
+}
+
+// Async pipelines are created on a blocking worker thread, so compiling them doesn't hold up the guest's frame loop.
+pub struct PipelineFuture<P: CorePipeline> {
+    instance: Arc<wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>>,
+    task: Option<tokio::task::JoinHandle<Result<Pipeline<P>, PipelineError>>>,
+    result: Option<Result<Pipeline<P>, PipelineError>>,
+}
+
+impl<P: CorePipeline> PipelineFuture<P> {
+    fn take_result(&mut self) -> Option<Result<Pipeline<P>, PipelineError>> {
+        if self.task.as_ref().is_some_and(|task| task.is_finished()) {
+            let task = self.task.take().unwrap();
+            self.result = task
+                .now_or_never()
+                .map(|result| result.unwrap_or_else(|e| Err(PipelineError::from_join_error(e))));
+        }
+        self.result.take()
+    }
+}
+
+#[async_trait::async_trait]
+impl<P: CorePipeline> preview2::Subscribe for PipelineFuture<P> {
+    async fn ready(&mut self) {
+        if let Some(task) = self.task.take() {
+            self.result = Some(
+                task.await
+                    .unwrap_or_else(|e| Err(PipelineError::from_join_error(e))),
+            );
+        }
+    }
+}
+
+// the guest never got the pipeline, so nothing else will release it.
+impl<P: CorePipeline> Drop for PipelineFuture<P> {
+    fn drop(&mut self) {
+        if let Some(Ok(pipeline)) = self.result.take() {
+            pipeline.inner.release(&self.instance);
+        }
+        if let Some(task) = self.task.take() {
+            let instance = Arc::clone(&self.instance);
+            tokio::spawn(async move {
+                if let Ok(Ok(pipeline)) = task.await {
+                    pipeline.inner.release(&instance);
+                }
+            });
+        }
+    }
+}
+
+pub type RenderPipelineFuture = PipelineFuture<wgpu_core::id::RenderPipelineId>;
+pub type ComputePipelineFuture = PipelineFuture<wgpu_core::id::ComputePipelineId>;
+
 pub struct Buffer {
     buffer: wgpu_core::id::BufferId,
-    mapped: Option<RemoteBuffer>,
+    // wgpu-core can't tell us what a buffer was created with, so we hold on to it for the getters.
+    descriptor: wgpu_core::resource::BufferDescriptor<'static>,
+    // wgpu-core checks these itself, except for the commands that never reach it.
+    valid: bool,
+    destroyed: bool,
+    mapped_ranges: Vec<MappedRange>,
+    // shared with the map_async callback, which runs without table access.
+    map_state: Arc<Mutex<webgpu::GpuBufferMapState>>,
+    error_sink: ErrorSink,
+    label: String,
+}
+
+impl Buffer {
+    // https://www.w3.org/TR/webgpu/#abstract-opdef-unmap
+    fn detach_mapped_ranges(&mut self) {
+        for mapped_range in self.mapped_ranges.drain(..) {
+            *mapped_range.0.lock().unwrap() = None;
+        }
+        *self.map_state.lock().unwrap() = webgpu::GpuBufferMapState::Unmapped;
+    }
+
+    // https://www.w3.org/TR/webgpu/#dom-gpucommandencoder-clearbuffer
+    // wgpu-core takes a missing size to mean the rest of the buffer, so it can't be asked to clear nothing. Empty clears are checked here instead.
+    fn validate_empty_clear(&self, offset: u64) -> Result<(), ValidationError> {
+        if !self.valid || self.destroyed {
+            return Err(ValidationError(format!(
+                "buffer \"{}\" is invalid or destroyed",
+                self.label
+            )));
+        }
+        if !self
+            .descriptor
+            .usage
+            .contains(wgpu_types::BufferUsages::COPY_DST)
+        {
+            return Err(ValidationError(format!(
+                "buffer \"{}\" is missing the copy-dst usage",
+                self.label
+            )));
+        }
+        if !offset.is_multiple_of(wgpu_types::COPY_BUFFER_ALIGNMENT) {
+            return Err(ValidationError(format!(
+                "clear offset {offset} is not a multiple of {}",
+                wgpu_types::COPY_BUFFER_ALIGNMENT
+            )));
+        }
+        if offset > self.descriptor.size {
+            return Err(ValidationError(format!(
+                "clear offset {offset} is past the end of a buffer of {} bytes",
+                self.descriptor.size
+            )));
+        }
+        Ok(())
+    }
 }
 
-#[derive(Clone, Copy)]
+pub struct Texture {
+    texture: wgpu_core::id::TextureId,
+    // surface textures belong to their surface, and go away when it presents.
+    from_surface: bool,
+    // wgpu-core can't tell us what a texture was created with, so we hold on to it for the getters.
+    descriptor: wgpu_core::resource::TextureDescriptor<'static>,
+    error_sink: ErrorSink,
+    label: String,
+}
+
+pub struct ShaderModule {
+    shader_module: wgpu_core::id::ShaderModuleId,
+    label: String,
+    compilation_messages: Vec<CompilationMessage>,
+}
+
+// https://www.w3.org/TR/webgpu/#gpucompilationinfo
+pub struct CompilationInfo {
+    messages: Vec<CompilationMessage>,
+}
+
+// https://www.w3.org/TR/webgpu/#gpucompilationmessage
+#[derive(Clone)]
+pub struct CompilationMessage {
+    message: String,
+    type_: webgpu::GpuCompilationMessageType,
+    line_num: u64,
+    line_pos: u64,
+    offset: u64,
+    length: u64,
+}
+
+impl CompilationMessage {
+    // wgpu-core stops at the first error and doesn't report warnings, so a module has at most one message.
+    fn from_core(error: &wgpu_core::pipeline::CreateShaderModuleError) -> Self {
+        use wgpu_core::pipeline::CreateShaderModuleError;
+        let location = match error {
+            CreateShaderModuleError::Parsing(e) => {
+                e.inner.location(&e.source).map(|l| (&e.source, l))
+            }
+            CreateShaderModuleError::Validation(e) => {
+                e.inner.location(&e.source).map(|l| (&e.source, l))
+            }
+            _ => None,
+        };
+        // errors that aren't about a specific part of the code use 0 for all positions.
+        let mut message = CompilationMessage {
+            message: Error::from_core(error).message,
+            type_: webgpu::GpuCompilationMessageType::Error,
+            line_num: 0,
+            line_pos: 0,
+            offset: 0,
+            length: 0,
+        };
+        // naga counts in bytes and chars, but webgpu counts in utf-16 code units.
+        if let Some((source, location)) = location {
+            let start = location.offset as usize;
+            let end = start + location.length as usize;
+            let line_start = source[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+            message.line_num = location.line_number.into();
+            message.line_pos = source[line_start..start].encode_utf16().count() as u64 + 1;
+            message.offset = source[..start].encode_utf16().count() as u64;
+            message.length = source[start..end].encode_utf16().count() as u64;
+        }
+        message
+    }
+}
+
+pub struct QuerySet {
+    query_set: wgpu_core::id::QuerySetId,
+    // query_set_drop panics on error ids, unlike the other *_drop calls.
+    valid: bool,
+    // destroy drops the wgpu-core query set early, after which its id must not reach wgpu-core again.
+    destroyed: bool,
+    type_: webgpu::GpuQueryType,
+    count: u32,
+    label: String,
+}
+
+impl QuerySet {
+    fn id(&self) -> Result<wgpu_core::id::QuerySetId, ValidationError> {
+        if self.destroyed {
+            return Err(ValidationError(format!(
+                "query set \"{}\" is destroyed",
+                self.label
+            )));
+        }
+        Ok(self.query_set)
+    }
+}
+
+// For resources that are just a wgpu-core object. wgpu-core gets the label at creation, but has no way to read it back or change it, so we keep our own copy.
+pub struct Labeled<T> {
+    inner: T,
+    label: String,
+}
+
+impl<T> Labeled<T> {
+    fn new(inner: T, label: Option<String>) -> Self {
+        // https://www.w3.org/TR/webgpu/#dom-gpuobjectdescriptorbase-label
+        Self {
+            inner,
+            label: label.unwrap_or_default(),
+        }
+    }
+}
+
+pub type TextureView = Labeled<wgpu_core::id::TextureViewId>;
+pub type Sampler = Labeled<wgpu_core::id::SamplerId>;
+pub type BindGroupLayout = Labeled<wgpu_core::id::BindGroupLayoutId>;
+pub type BindGroup = Labeled<wgpu_core::id::BindGroupId>;
+pub type PipelineLayout = Labeled<wgpu_core::id::PipelineLayoutId>;
+pub type CommandBuffer = Labeled<wgpu_core::id::CommandBufferId>;
+pub type RenderBundle = Labeled<wgpu_core::id::RenderBundleId>;
+
+// Like Labeled, but pipelines can still produce errors after they're created, from get-bind-group-layout.
+pub struct Pipeline<T> {
+    inner: T,
+    error_sink: ErrorSink,
+    label: String,
+}
+
+impl<T> Pipeline<T> {
+    fn new(inner: T, error_sink: ErrorSink, label: Option<String>) -> Self {
+        Self {
+            inner,
+            error_sink,
+            label: label.unwrap_or_default(),
+        }
+    }
+}
+
+pub type RenderPipeline = Pipeline<wgpu_core::id::RenderPipelineId>;
+pub type ComputePipeline = Pipeline<wgpu_core::id::ComputePipelineId>;
+
+pub struct BufferMapFuture {
+    instance: Arc<wgpu_core::global::Global<wgpu_core::identity::IdentityManagerFactory>>,
+    device: wgpu_core::id::DeviceId,
+    error_sink: ErrorSink,
+    sender: Sender<HostEvent>,
+    result: Arc<Mutex<Option<Result<(), String>>>>,
+    // the map callback wakes the future once the result is in.
+    notify: Arc<tokio::sync::Notify>,
+    poll: Option<tokio::task::JoinHandle<()>>,
+}
+
+#[async_trait::async_trait]
+impl preview2::Subscribe for BufferMapFuture {
+    async fn ready(&mut self) {
+        loop {
+            if self.result.lock().unwrap().is_some() {
+                return;
+            }
+            // wgpu-core only calls map callbacks while the device is being polled. Waiting on the device blocks until the gpu is done with the buffer, so it happens on a worker thread.
+            if self.poll.as_ref().map_or(true, |poll| poll.is_finished()) {
+                let instance = Arc::clone(&self.instance);
+                let device = self.device;
+                let error_sink = self.error_sink.clone();
+                let sender = self.sender.clone();
+                let result = Arc::clone(&self.result);
+                let notify = Arc::clone(&self.notify);
+                self.poll = Some(tokio::task::spawn_blocking(move || {
+                    if let Err(e) =
+                        instance.device_poll::<crate::Backend>(device, wgpu_types::Maintain::Wait)
+                    {
+                        // the map can't finish on a device that can't be polled.
+                        result
+                            .lock()
+                            .unwrap()
+                            .get_or_insert_with(|| Err(e.to_string()));
+                        error_sink.dispatch(&sender, e);
+                    }
+                    // also wakes the future when the poll didn't resolve the map, so it polls again.
+                    notify.notify_one();
+                }));
+            }
+            self.notify.notified().await;
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct Device {
     pub device: wgpu_core::id::DeviceId,
     // only needed when calling surface.get_capabilities in connect_graphics_context. If table would have a way to get parent from child, we could get it from device.
     pub adapter: wgpu_core::id::AdapterId,
+    pub error_sink: ErrorSink,
+    pub label: String,
+    // every handle to the queue is the same queue, so they share its label.
+    pub queue_label: Arc<Mutex<String>>,
+}
+
+// wgpu-core checks that passes don't pop more debug groups than they push, but none of the encoders check that everything pushed gets popped, so we count them ourselves.
+pub struct CommandEncoder {
+    command_encoder: wgpu_core::id::CommandEncoderId,
+    error_sink: ErrorSink,
+    label: String,
+    debug_group_depth: u32,
+}
+
+pub struct RenderPassEncoder {
+    render_pass: wgpu_core::command::RenderPass,
+    error_sink: ErrorSink,
+    label: String,
+    debug_group_depth: u32,
+    // https://www.w3.org/TR/webgpu/#invalidate
+    // wgpu-core has no way to mark a pass invalid, so we hold on to why it is, and report it when the pass ends.
+    invalid: Option<String>,
+}
+
+impl RenderPassEncoder {
+    // none once the pass is invalid, since nothing recorded into it will run.
+    fn pass(&mut self) -> Option<&mut wgpu_core::command::RenderPass> {
+        match self.invalid {
+            None => Some(&mut self.render_pass),
+            Some(_) => None,
+        }
+    }
+
+    // only the first reason is reported.
+    fn invalidate(&mut self, error: ValidationError) {
+        self.invalid.get_or_insert(error.to_string());
+    }
+}
+
+pub struct RenderBundleEncoder {
+    render_bundle_encoder: wgpu_core::command::RenderBundleEncoder,
+    error_sink: ErrorSink,
+    label: String,
+    debug_group_depth: u32,
+}
+
+pub struct ComputePassEncoder {
+    compute_pass: wgpu_core::command::ComputePass,
+    error_sink: ErrorSink,
+    label: String,
+    debug_group_depth: u32,
+    // same as RenderPassEncoder::invalid.
+    invalid: Option<String>,
+    // unlike render passes, end borrows the pass, so it can be called again.
+    ended: bool,
+}
+
+impl ComputePassEncoder {
+    // same as RenderPassEncoder::pass.
+    fn pass(&mut self) -> Option<&mut wgpu_core::command::ComputePass> {
+        match self.invalid {
+            None => Some(&mut self.compute_pass),
+            Some(_) => None,
+        }
+    }
+
+    fn invalidate(&mut self, error: ValidationError) {
+        self.invalid.get_or_insert(error.to_string());
+    }
 }
 
 impl webgpu::Host for HostState {
@@ -55,16 +1198,24 @@ impl webgpu::Host for HostState {
 }
 
 impl webgpu::HostRemoteBuffer for HostState {
+    // detached ranges are empty.
     fn length(&mut self, buffer: Resource<webgpu::RemoteBuffer>) -> wasmtime::Result<u32> {
-        let buffer = self.table.get(&buffer).unwrap();
-        let len = buffer.mapped.as_ref().unwrap().len;
+        let mapped_range = self.table.get(&buffer).unwrap().0.lock().unwrap();
+        let len = mapped_range
+            .as_ref()
+            .map_or(0, |remote_buffer| remote_buffer.len);
         Ok(len as u32)
     }
 
     fn get(&mut self, buffer: Resource<webgpu::RemoteBuffer>, i: u32) -> wasmtime::Result<u8> {
-        let buffer = self.table.get(&buffer).unwrap();
-        let remote_buffer = buffer.mapped.as_ref().unwrap();
-        let val = remote_buffer.slice()[i as usize];
+        let mapped_range = self.table.get(&buffer).unwrap().0.lock().unwrap();
+        let remote_buffer = mapped_range
+            .as_ref()
+            .context("remote buffer read after its buffer was unmapped")?;
+        let val = *remote_buffer
+            .slice()
+            .get(i as usize)
+            .context("remote buffer read out of bounds")?;
         Ok(val)
     }
 
@@ -74,13 +1225,19 @@ impl webgpu::HostRemoteBuffer for HostState {
         i: u32,
         val: u8,
     ) -> wasmtime::Result<()> {
-        let buffer = self.table.get_mut(&buffer).unwrap();
-        let remote_buffer = buffer.mapped.as_mut().unwrap();
-        remote_buffer.slice_mut()[i as usize] = val;
+        let mut mapped_range = self.table.get(&buffer).unwrap().0.lock().unwrap();
+        let remote_buffer = mapped_range
+            .as_mut()
+            .context("remote buffer written after its buffer was unmapped")?;
+        *remote_buffer
+            .slice_mut()
+            .get_mut(i as usize)
+            .context("remote buffer written out of bounds")? = val;
         Ok(())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::RemoteBuffer>) -> wasmtime::Result<()> {
+    fn drop(&mut self, buffer: Resource<webgpu::RemoteBuffer>) -> wasmtime::Result<()> {
+        self.table.delete(buffer).unwrap();
         Ok(())
     }
 }
@@ -91,40 +1248,122 @@ impl webgpu::HostGpuDevice for HostState {
         device: Resource<Device>,
         context: Resource<GraphicsContext>,
     ) -> wasmtime::Result<()> {
+        let host_device = self.table.get(&device).unwrap();
+
+        if let Some((width, height)) = self.table.get(&context).unwrap().offscreen_size {
+            // https://www.w3.org/TR/webgpu/#abstract-opdef-allocate-a-new-context-texture
+            let descriptor = wgpu_types::TextureDescriptor {
+                label: None,
+                size: wgpu_types::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu_types::TextureDimension::D2,
+                // what get-preferred-canvas-format answers, so guests that follow it match the texture.
+                format: self.preferred_canvas_format,
+                // copy-src so unattended guests can read back what they rendered.
+                usage: wgpu_types::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu_types::TextureUsages::COPY_SRC,
+                view_formats: vec![],
+            };
+            let texture = self.create_offscreen_texture(host_device, &descriptor);
+            let device = host_device.clone();
+            let context = self.table.get_mut(&context).unwrap();
+            context.kind = Some(GraphicsContextKind::WebgpuOffscreen {
+                texture,
+                device,
+                descriptor,
+            });
+            return Ok(());
+        }
+
+        let window = self
+            .table
+            .get(&context)
+            .unwrap()
+            .window
+            .clone()
+            .context("graphics contexts have to be connected to a canvas first")?;
         let surface = self.instance.instance_create_surface(
-            self.window.raw_display_handle(),
-            self.window.raw_window_handle(),
+            window.raw_display_handle(),
+            window.raw_window_handle(),
             (),
         );
 
-        let host_device = self.table.get(&device).unwrap();
-
-        let mut size = self.window.inner_size();
+        let mut size = window.inner_size();
         size.width = size.width.max(1);
         size.height = size.height.max(1);
 
-        let swapchain_capabilities = self
+        // fails when the device's adapter can't present to the window at all.
+        let swapchain_capabilities = match self
             .instance
             .surface_get_capabilities::<crate::Backend>(surface, host_device.adapter)
-            .unwrap();
-        let swapchain_format = swapchain_capabilities.formats[0];
+        {
+            Ok(swapchain_capabilities) => swapchain_capabilities,
+            Err(e) => {
+                self.instance.surface_drop(surface);
+                return Err(e).context("the device can't present to this canvas");
+            }
+        };
 
+        // what the surface shows until the guest configures it, picked like configure-context-desc's defaults.
         let config = wgpu_types::SurfaceConfiguration {
             usage: wgpu_types::TextureUsages::RENDER_ATTACHMENT,
-            format: swapchain_format,
+            format: self.preferred_canvas_format,
             width: size.width,
             height: size.height,
             present_mode: wgpu_types::PresentMode::Fifo,
-            alpha_mode: swapchain_capabilities.alpha_modes[0],
-            view_formats: vec![swapchain_format],
+            // https://www.w3.org/TR/webgpu/#dom-gpucanvasconfiguration-alphamode
+            alpha_mode: match swapchain_capabilities
+                .alpha_modes
+                .contains(&wgpu_types::CompositeAlphaMode::Opaque)
+            {
+                true => wgpu_types::CompositeAlphaMode::Opaque,
+                false => wgpu_types::CompositeAlphaMode::Auto,
+            },
+            view_formats: vec![],
         };
 
-        self.instance
-            .surface_configure::<crate::Backend>(surface, host_device.device, &config);
+        self.configure_surface(host_device, surface, &config);
+        let device = host_device.clone();
+
+        let resized = Arc::new(Mutex::new(None));
+        let mut receiver = self.sender.subscribe();
+        // weak, so the task ends once the context is gone.
+        let resized_weak = Arc::downgrade(&resized);
+        let window_id = window.id();
+
+        tokio::spawn(async move {
+            loop {
+                let event = match receiver.recv().await {
+                    Ok(event) => event,
+                    // a missed resize only matters if it was the last one, and the surface catches up once it's outdated.
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return,
+                };
+                let Some(resized) = resized_weak.upgrade() else {
+                    return;
+                };
+                if let HostEvent::CanvasResizeEvent(window, event) = event {
+                    if window == window_id {
+                        *resized.lock().unwrap() = Some((event.width, event.height));
+                    }
+                }
+            }
+        });
 
         let context = self.table.get_mut(&context).unwrap();
 
-        context.kind = Some(GraphicsContextKind::Webgpu(surface));
+        context.kind = Some(GraphicsContextKind::Webgpu {
+            surface,
+            device,
+            config,
+            resized,
+            window,
+        });
 
         Ok(())
     }
@@ -133,10 +1372,12 @@ impl webgpu::HostGpuDevice for HostState {
         &mut self,
         device: Resource<Device>,
         descriptor: Option<webgpu::GpuCommandEncoderDescriptor>,
-    ) -> wasmtime::Result<Resource<wgpu_core::id::CommandEncoderId>> {
+    ) -> wasmtime::Result<Resource<webgpu::GpuCommandEncoder>> {
         let host_daq = self.table.get(&device).unwrap();
+        let label = descriptor.as_ref().and_then(|d| d.label.clone());
 
-        let command_encoder = core_result(
+        let command_encoder = self.handle_core_result(
+            &host_daq.error_sink,
             self.instance
                 .device_create_command_encoder::<crate::Backend>(
                     host_daq.device,
@@ -145,10 +1386,15 @@ impl webgpu::HostGpuDevice for HostState {
                         .unwrap_or_default(),
                     (),
                 ),
-        )
-        .unwrap();
+        );
+        let command_encoder = CommandEncoder {
+            command_encoder,
+            error_sink: host_daq.error_sink.clone(),
+            label: label.unwrap_or_default(),
+            debug_group_depth: 0,
+        };
 
-        Ok(self.table.push_child(command_encoder, &device).unwrap())
+        Ok(self.table.push(command_encoder).unwrap())
     }
 
     fn create_shader_module(
@@ -157,16 +1403,23 @@ impl webgpu::HostGpuDevice for HostState {
         descriptor: webgpu::GpuShaderModuleDescriptor,
     ) -> wasmtime::Result<Resource<webgpu::GpuShaderModule>> {
         let device = self.table.get(&device).unwrap();
+        let label = descriptor.label.clone();
 
         let code =
             wgpu_core::pipeline::ShaderModuleSource::Wgsl(Cow::Owned(descriptor.code.to_owned()));
-        let shader = core_result(self.instance.device_create_shader_module::<crate::Backend>(
+        let (shader, error) = self.instance.device_create_shader_module::<crate::Backend>(
             device.device,
             &descriptor.to_core(&self.table),
             code,
             (),
-        ))
-        .unwrap();
+        );
+        let compilation_messages = error.iter().map(CompilationMessage::from_core).collect();
+        let shader = self.handle_core_result(&device.error_sink, (shader, error));
+        let shader = ShaderModule {
+            shader_module: shader,
+            label: label.unwrap_or_default(),
+            compilation_messages,
+        };
 
         Ok(self.table.push(shader).unwrap())
     }
@@ -175,34 +1428,27 @@ impl webgpu::HostGpuDevice for HostState {
         &mut self,
         device: Resource<Device>,
         descriptor: webgpu::GpuRenderPipelineDescriptor,
-    ) -> wasmtime::Result<Resource<wgpu_core::id::RenderPipelineId>> {
+    ) -> wasmtime::Result<Resource<webgpu::GpuRenderPipeline>> {
         let host_device = self.table.get(&device).unwrap();
-
-        let descriptor = descriptor.to_core(&self.table);
-
-        let implicit_pipeline_ids = match descriptor.layout {
-            Some(_) => None,
-            None => Some(wgpu_core::device::ImplicitPipelineIds {
-                root_id: (),
-                group_ids: &[(); wgpu_core::MAX_BIND_GROUPS],
-            }),
-        };
-        let render_pipeline = core_result(
-            self.instance
-                .device_create_render_pipeline::<crate::Backend>(
-                    host_device.device,
-                    &descriptor,
-                    (),
-                    implicit_pipeline_ids,
-                ),
-        )
-        .unwrap();
-
-        Ok(self.table.push_child(render_pipeline, &device).unwrap())
+        let label = descriptor.label.clone();
+        let module = self
+            .table
+            .get(&descriptor.vertex.module)
+            .unwrap()
+            .shader_module;
+        let render_pipeline = self.create_pipeline(
+            host_device,
+            descriptor.try_to_core(&self.table),
+            module,
+            label,
+        );
+        Ok(self.table.push(render_pipeline).unwrap())
     }
 
     fn queue(&mut self, device: Resource<Device>) -> wasmtime::Result<Resource<Device>> {
-        Ok(Resource::new_own(device.rep()))
+        // the queue gets its own entry, so that dropping it doesn't drop the device.
+        let queue = self.table.get(&device).unwrap().clone();
+        Ok(self.table.push(queue).unwrap())
     }
 
     fn features(
@@ -219,13 +1465,26 @@ impl webgpu::HostGpuDevice for HostState {
 
     fn limits(
         &mut self,
-        _device: Resource<webgpu::GpuDevice>,
+        device: Resource<webgpu::GpuDevice>,
     ) -> wasmtime::Result<Resource<webgpu::GpuSupportedLimits>> {
-        todo!()
+        let device = self.table.get(&device).unwrap();
+        let limits = self
+            .instance
+            .device_limits::<crate::Backend>(device.device)
+            .unwrap();
+        Ok(self.table.push(limits).unwrap())
     }
 
-    fn destroy(&mut self, _device: Resource<webgpu::GpuDevice>) -> wasmtime::Result<()> {
-        todo!()
+    fn destroy(&mut self, device: Resource<webgpu::GpuDevice>) -> wasmtime::Result<()> {
+        let device = self.table.get(&device).unwrap();
+        self.instance
+            .device_destroy::<crate::Backend>(device.device);
+        // https://www.w3.org/TR/webgpu/#dom-gpudevice-destroy
+        device.error_sink.lose(
+            webgpu::GpuDeviceLostReason::Destroyed,
+            "device was destroyed".to_string(),
+        );
+        Ok(())
     }
 
     fn create_buffer(
@@ -235,16 +1494,28 @@ impl webgpu::HostGpuDevice for HostState {
     ) -> wasmtime::Result<Resource<webgpu::GpuBuffer>> {
         let device = self.table.get(&device).unwrap();
 
-        let buffer = core_result(self.instance.device_create_buffer::<crate::Backend>(
-            device.device,
-            &descriptor.to_core(&self.table),
-            (),
-        ))
-        .unwrap();
+        let label = descriptor.label.clone();
+        let map_state = match descriptor.mapped_at_creation {
+            Some(true) => webgpu::GpuBufferMapState::Mapped,
+            _ => webgpu::GpuBufferMapState::Unmapped,
+        };
+
+        let descriptor = descriptor.to_core(&self.table);
+        let (buffer, error) =
+            self.instance
+                .device_create_buffer::<crate::Backend>(device.device, &descriptor, ());
+        let valid = error.is_none();
+        let buffer = self.handle_core_result(&device.error_sink, (buffer, error));
 
         let buffer = Buffer {
             buffer,
-            mapped: None,
+            descriptor,
+            valid,
+            destroyed: false,
+            mapped_ranges: vec![],
+            map_state: Arc::new(Mutex::new(map_state)),
+            error_sink: device.error_sink.clone(),
+            label: label.unwrap_or_default(),
         };
 
         Ok(self.table.push(buffer).unwrap())
@@ -255,13 +1526,48 @@ impl webgpu::HostGpuDevice for HostState {
         device: Resource<webgpu::GpuDevice>,
         descriptor: webgpu::GpuTextureDescriptor,
     ) -> wasmtime::Result<Resource<webgpu::GpuTexture>> {
-        let device = *self.table.get(&device).unwrap();
-        let texture = core_result(self.instance.device_create_texture::<crate::Backend>(
-            device.device,
-            &descriptor.to_core(&self.table),
-            (),
-        ))
-        .unwrap();
+        let device = self.table.get(&device).unwrap();
+        let label = descriptor.label.clone();
+        let (dimension, format) = (descriptor.dimension, descriptor.format);
+        let (texture, descriptor) = match descriptor.try_to_core(&self.table) {
+            Ok(descriptor) => {
+                let texture = self.handle_core_result(
+                    &device.error_sink,
+                    self.instance.device_create_texture::<crate::Backend>(
+                        device.device,
+                        &descriptor,
+                        (),
+                    ),
+                );
+                (texture, descriptor)
+            }
+            Err(e) => {
+                self.handle_error(&device.error_sink, e);
+                let descriptor = wgpu_types::TextureDescriptor {
+                    label: label.clone().map(|l| l.into()),
+                    size: wgpu_types::Extent3d {
+                        width: 0,
+                        height: 0,
+                        depth_or_array_layers: 0,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: dimension.into(),
+                    format: format.into(),
+                    usage: wgpu_types::TextureUsages::empty(),
+                    view_formats: vec![],
+                };
+                let texture = self.create_invalid_texture(device.device, &descriptor);
+                (texture, descriptor)
+            }
+        };
+        let texture = Texture {
+            texture,
+            from_surface: false,
+            descriptor,
+            error_sink: device.error_sink.clone(),
+            label: label.unwrap_or_default(),
+        };
 
         Ok(self.table.push(texture).unwrap())
     }
@@ -274,15 +1580,18 @@ impl webgpu::HostGpuDevice for HostState {
         let device = self.table.get(&device).unwrap();
 
         let descriptor = descriptor.unwrap();
+        let label = descriptor.label.clone();
 
-        let sampler = core_result(self.instance.device_create_sampler::<crate::Backend>(
-            device.device,
-            &descriptor.to_core(&self.table),
-            (),
-        ))
-        .unwrap();
+        let sampler = self.handle_core_result(
+            &device.error_sink,
+            self.instance.device_create_sampler::<crate::Backend>(
+                device.device,
+                &descriptor.to_core(&self.table),
+                (),
+            ),
+        );
 
-        Ok(self.table.push(sampler).unwrap())
+        Ok(self.table.push(Labeled::new(sampler, label)).unwrap())
     }
 
     fn import_external_texture(
@@ -299,18 +1608,22 @@ impl webgpu::HostGpuDevice for HostState {
         descriptor: webgpu::GpuBindGroupLayoutDescriptor,
     ) -> wasmtime::Result<Resource<webgpu::GpuBindGroupLayout>> {
         let device = self.table.get(&device).unwrap();
+        let label = descriptor.label.clone();
 
-        let bind_group_layout = core_result(
+        let bind_group_layout = self.handle_core_result(
+            &device.error_sink,
             self.instance
                 .device_create_bind_group_layout::<crate::Backend>(
                     device.device,
                     &descriptor.to_core(&self.table),
                     (),
                 ),
-        )
-        .unwrap();
+        );
 
-        Ok(self.table.push(bind_group_layout).unwrap())
+        Ok(self
+            .table
+            .push(Labeled::new(bind_group_layout, label))
+            .unwrap())
     }
 
     fn create_pipeline_layout(
@@ -318,19 +1631,23 @@ impl webgpu::HostGpuDevice for HostState {
         device: Resource<webgpu::GpuDevice>,
         descriptor: webgpu::GpuPipelineLayoutDescriptor,
     ) -> wasmtime::Result<Resource<webgpu::GpuPipelineLayout>> {
-        let device = *self.table.get(&device).unwrap();
+        let device = self.table.get(&device).unwrap();
+        let label = descriptor.label.clone();
 
-        let pipeline_layout = core_result(
+        let pipeline_layout = self.handle_core_result(
+            &device.error_sink,
             self.instance
                 .device_create_pipeline_layout::<crate::Backend>(
                     device.device,
                     &descriptor.to_core(&self.table),
                     (),
                 ),
-        )
-        .unwrap();
+        );
 
-        Ok(self.table.push(pipeline_layout).unwrap())
+        Ok(self
+            .table
+            .push(Labeled::new(pipeline_layout, label))
+            .unwrap())
     }
 
     fn create_bind_group(
@@ -338,100 +1655,200 @@ impl webgpu::HostGpuDevice for HostState {
         device: Resource<webgpu::GpuDevice>,
         descriptor: webgpu::GpuBindGroupDescriptor,
     ) -> wasmtime::Result<Resource<webgpu::GpuBindGroup>> {
-        let device = *self.table.get(&device).unwrap();
+        let device = self.table.get(&device).unwrap();
+        let label = descriptor.label.clone();
 
-        let bind_group = core_result(self.instance.device_create_bind_group::<crate::Backend>(
-            device.device,
-            &descriptor.to_core(&self.table),
-            (),
-        ))
-        .unwrap();
+        let bind_group = self.handle_core_result(
+            &device.error_sink,
+            self.instance.device_create_bind_group::<crate::Backend>(
+                device.device,
+                &descriptor.to_core(&self.table),
+                (),
+            ),
+        );
 
-        Ok(self.table.push(bind_group).unwrap())
+        Ok(self.table.push(Labeled::new(bind_group, label)).unwrap())
     }
 
     fn create_compute_pipeline(
         &mut self,
-        _device: Resource<webgpu::GpuDevice>,
-        _descriptor: webgpu::GpuComputePipelineDescriptor,
+        device: Resource<webgpu::GpuDevice>,
+        descriptor: webgpu::GpuComputePipelineDescriptor,
     ) -> wasmtime::Result<Resource<webgpu::GpuComputePipeline>> {
-        todo!()
+        let host_device = self.table.get(&device).unwrap();
+        let label = descriptor.label.clone();
+        let module = self
+            .table
+            .get(&descriptor.compute.module)
+            .unwrap()
+            .shader_module;
+        let compute_pipeline = self.create_pipeline(
+            host_device,
+            descriptor.try_to_core(&self.table),
+            module,
+            label,
+        );
+        Ok(self.table.push(compute_pipeline).unwrap())
     }
 
-    // fn create_compute_pipeline_async(
-    //     &mut self,
-    //     self_: Resource<webgpu::GpuDevice>,
-    //     descriptor: webgpu::GpuComputePipelineDescriptor,
-    // ) -> wasmtime::Result<Resource<webgpu::GpuComputePipeline>> {
-    //     todo!()
-    // }
+    fn create_compute_pipeline_async(
+        &mut self,
+        device: Resource<webgpu::GpuDevice>,
+        descriptor: webgpu::GpuComputePipelineDescriptor,
+    ) -> wasmtime::Result<Resource<webgpu::GpuComputePipelineFuture>> {
+        let host_device = self.table.get(&device).unwrap();
+        let label = descriptor.label.clone();
+        let future = self.create_pipeline_async::<wgpu_core::id::ComputePipelineId>(
+            host_device,
+            descriptor.try_to_core(&self.table),
+            label,
+        );
+        Ok(self.table.push(future).unwrap())
+    }
 
-    // fn create_render_pipeline_async(
-    //     &mut self,
-    //     self_: Resource<webgpu::GpuDevice>,
-    //     descriptor: webgpu::GpuRenderPipelineDescriptor,
-    // ) -> wasmtime::Result<Resource<wgpu_core::id::RenderPipelineId>> {
-    //     todo!()
-    // }
+    fn create_render_pipeline_async(
+        &mut self,
+        device: Resource<webgpu::GpuDevice>,
+        descriptor: webgpu::GpuRenderPipelineDescriptor,
+    ) -> wasmtime::Result<Resource<webgpu::GpuRenderPipelineFuture>> {
+        let host_device = self.table.get(&device).unwrap();
+        let label = descriptor.label.clone();
+        let future = self.create_pipeline_async::<wgpu_core::id::RenderPipelineId>(
+            host_device,
+            descriptor.try_to_core(&self.table),
+            label,
+        );
+        Ok(self.table.push(future).unwrap())
+    }
 
     fn create_render_bundle_encoder(
         &mut self,
-        _device: Resource<webgpu::GpuDevice>,
-        _descriptor: webgpu::GpuRenderBundleEncoderDescriptor,
+        device: Resource<webgpu::GpuDevice>,
+        descriptor: webgpu::GpuRenderBundleEncoderDescriptor,
     ) -> wasmtime::Result<Resource<webgpu::GpuRenderBundleEncoder>> {
-        todo!()
+        let device = self.table.get(&device).unwrap();
+        let label = descriptor.label.clone();
+        let render_bundle_encoder = match wgpu_core::command::RenderBundleEncoder::new(
+            &descriptor.to_core(&self.table),
+            device.device,
+            None,
+        ) {
+            Ok(render_bundle_encoder) => render_bundle_encoder,
+            Err(e) => {
+                self.handle_error(&device.error_sink, e);
+                // same as wgpu, a dummy encoder makes finish produce an invalid bundle.
+                wgpu_core::command::RenderBundleEncoder::dummy(device.device)
+            }
+        };
+        let render_bundle_encoder = RenderBundleEncoder {
+            render_bundle_encoder,
+            error_sink: device.error_sink.clone(),
+            label: label.unwrap_or_default(),
+            debug_group_depth: 0,
+        };
+        Ok(self.table.push(render_bundle_encoder).unwrap())
     }
 
     fn create_query_set(
         &mut self,
-        _device: Resource<webgpu::GpuDevice>,
-        _descriptor: webgpu::GpuQuerySetDescriptor,
+        device: Resource<webgpu::GpuDevice>,
+        descriptor: webgpu::GpuQuerySetDescriptor,
     ) -> wasmtime::Result<Resource<webgpu::GpuQuerySet>> {
-        todo!()
+        let device = self.table.get(&device).unwrap();
+        let type_ = descriptor.type_;
+        let count = descriptor.count;
+        let label = descriptor.label.clone();
+        let (query_set, error) = self.instance.device_create_query_set::<crate::Backend>(
+            device.device,
+            &descriptor.to_core(&self.table),
+            (),
+        );
+        let valid = error.is_none();
+        let query_set = self.handle_core_result(&device.error_sink, (query_set, error));
+        Ok(self
+            .table
+            .push(QuerySet {
+                query_set,
+                valid,
+                destroyed: false,
+                type_,
+                count,
+                label: label.unwrap_or_default(),
+            })
+            .unwrap())
     }
 
-    fn label(&mut self, _device: Resource<webgpu::GpuDevice>) -> wasmtime::Result<String> {
-        todo!()
+    fn label(&mut self, device: Resource<webgpu::GpuDevice>) -> wasmtime::Result<String> {
+        Ok(self.table.get(&device).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _device: Resource<webgpu::GpuDevice>,
-        _label: String,
+        device: Resource<webgpu::GpuDevice>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&device).unwrap().label = label;
+        Ok(())
     }
 
     fn lost(
         &mut self,
-        _device: Resource<webgpu::GpuDevice>,
-    ) -> wasmtime::Result<Resource<webgpu::GpuDeviceLostInfo>> {
-        todo!()
+        device: Resource<webgpu::GpuDevice>,
+    ) -> wasmtime::Result<Resource<webgpu::GpuDeviceLostFuture>> {
+        let device = self.table.get(&device).unwrap();
+        let lost_future = DeviceLostFuture {
+            instance: Arc::clone(&self.instance),
+            device: device.device,
+            error_sink: device.error_sink.clone(),
+        };
+        Ok(self.table.push(lost_future).unwrap())
     }
 
     fn push_error_scope(
         &mut self,
-        _device: Resource<webgpu::GpuDevice>,
-        _filter: webgpu::GpuErrorFilter,
+        device: Resource<webgpu::GpuDevice>,
+        filter: webgpu::GpuErrorFilter,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let device = self.table.get(&device).unwrap();
+        device.error_sink.scopes.lock().unwrap().push(ErrorScope {
+            filter,
+            error: None,
+        });
+        Ok(())
     }
 
     fn pop_error_scope(
         &mut self,
-        _device: Resource<webgpu::GpuDevice>,
-    ) -> wasmtime::Result<Resource<webgpu::GpuError>> {
-        todo!()
+        device: Resource<webgpu::GpuDevice>,
+    ) -> wasmtime::Result<Result<Option<Resource<webgpu::GpuError>>, String>> {
+        let device = self.table.get(&device).unwrap();
+        let scope = device.error_sink.scopes.lock().unwrap().pop();
+        // https://www.w3.org/TR/webgpu/#dom-gpudevice-poperrorscope
+        let Some(scope) = scope else {
+            return Ok(Err(
+                "pop-error-scope called with an empty error scope stack".to_string(),
+            ));
+        };
+        Ok(Ok(scope.error.map(|error| self.table.push(error).unwrap())))
     }
 
     fn onuncapturederror(
         &mut self,
-        _device: Resource<webgpu::GpuDevice>,
-    ) -> wasmtime::Result<Resource<webgpu::EventHandler>> {
-        todo!()
+        device: Resource<webgpu::GpuDevice>,
+    ) -> wasmtime::Result<Resource<webgpu::GpuUncapturedErrorListener>> {
+        let device = self.table.get(&device).unwrap();
+        let listener = UncapturedErrorListener {
+            device: device.device,
+            receiver: self.sender.subscribe(),
+            data: Default::default(),
+        };
+        Ok(self.table.push(listener).unwrap())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuDevice>) -> wasmtime::Result<()> {
+    fn drop(&mut self, device: Resource<webgpu::GpuDevice>) -> wasmtime::Result<()> {
+        let device = self.table.delete(device).unwrap();
+        device.error_sink.dropped.store(true, Ordering::Relaxed);
+        self.instance.device_drop::<crate::Backend>(device.device);
         Ok(())
     }
 }
@@ -440,10 +1857,19 @@ impl webgpu::HostGpuTexture for HostState {
     fn from_graphics_buffer(
         &mut self,
         buffer: Resource<GraphicsContextBuffer>,
-    ) -> wasmtime::Result<Resource<wgpu_core::id::TextureId>> {
+    ) -> wasmtime::Result<Resource<webgpu::GpuTexture>> {
         let host_buffer = self.table.delete(buffer).unwrap();
-        if let GraphicsContextBuffer::Webgpu(host_buffer) = host_buffer {
-            Ok(self.table.push(host_buffer).unwrap())
+        if let GraphicsContextBuffer::Webgpu(host_buffer, descriptor, error_sink, from_surface) =
+            host_buffer
+        {
+            let texture = Texture {
+                texture: host_buffer,
+                from_surface,
+                descriptor,
+                error_sink,
+                label: String::new(),
+            };
+            Ok(self.table.push(texture).unwrap())
         } else {
             panic!("Context not connected to webgpu");
         }
@@ -451,200 +1877,265 @@ impl webgpu::HostGpuTexture for HostState {
 
     fn create_view(
         &mut self,
-        texture: Resource<wgpu_core::id::TextureId>,
+        texture: Resource<webgpu::GpuTexture>,
         descriptor: Option<webgpu::GpuTextureViewDescriptor>,
-    ) -> wasmtime::Result<Resource<wgpu_core::id::TextureViewId>> {
-        let texture_id = *self.table.get(&texture).unwrap();
-        let texture_view = core_result(
+    ) -> wasmtime::Result<Resource<webgpu::GpuTextureView>> {
+        let texture = self.table.get(&texture).unwrap();
+        let label = descriptor.as_ref().and_then(|d| d.label.clone());
+        let texture_view = self.handle_core_result(
+            &texture.error_sink,
             self.instance.texture_create_view::<crate::Backend>(
-                texture_id,
+                texture.texture,
                 &descriptor
                     .map(|d| d.to_core(&self.table))
                     .unwrap_or_default(),
                 (),
             ),
-        )
-        .unwrap();
-        Ok(self.table.push(texture_view).unwrap())
+        );
+        Ok(self.table.push(Labeled::new(texture_view, label)).unwrap())
     }
 
-    fn drop(&mut self, _rep: Resource<wgpu_core::id::TextureId>) -> wasmtime::Result<()> {
-        // TODO:
+    fn drop(&mut self, texture: Resource<webgpu::GpuTexture>) -> wasmtime::Result<()> {
+        let texture = self.table.delete(texture).unwrap();
+        if !texture.from_surface {
+            self.instance
+                .texture_drop::<crate::Backend>(texture.texture, false);
+        }
         Ok(())
     }
 
-    fn destroy(&mut self, _self_: Resource<webgpu::GpuTexture>) -> wasmtime::Result<()> {
-        todo!()
+    fn destroy(&mut self, texture: Resource<webgpu::GpuTexture>) -> wasmtime::Result<()> {
+        let texture = self.table.get(&texture).unwrap();
+        // https://www.w3.org/TR/webgpu/#dom-gputexture-destroy
+        // the surface decides when its textures go away.
+        if texture.from_surface {
+            return Ok(());
+        }
+        if let Err(e) = self
+            .instance
+            .texture_destroy::<crate::Backend>(texture.texture)
+        {
+            self.handle_error(&texture.error_sink, e);
+        }
+        Ok(())
     }
 
     fn width(
         &mut self,
-        _self_: Resource<webgpu::GpuTexture>,
+        texture: Resource<webgpu::GpuTexture>,
     ) -> wasmtime::Result<webgpu::GpuIntegerCoordinateOut> {
-        todo!()
+        let texture = self.table.get(&texture).unwrap();
+        Ok(texture.descriptor.size.width)
     }
 
     fn height(
         &mut self,
-        _self_: Resource<webgpu::GpuTexture>,
+        texture: Resource<webgpu::GpuTexture>,
     ) -> wasmtime::Result<webgpu::GpuIntegerCoordinateOut> {
-        todo!()
+        let texture = self.table.get(&texture).unwrap();
+        Ok(texture.descriptor.size.height)
     }
 
     fn depth_or_array_layers(
         &mut self,
-        _self_: Resource<webgpu::GpuTexture>,
+        texture: Resource<webgpu::GpuTexture>,
     ) -> wasmtime::Result<webgpu::GpuIntegerCoordinateOut> {
-        todo!()
+        let texture = self.table.get(&texture).unwrap();
+        Ok(texture.descriptor.size.depth_or_array_layers)
     }
 
     fn mip_level_count(
         &mut self,
-        _self_: Resource<webgpu::GpuTexture>,
+        texture: Resource<webgpu::GpuTexture>,
     ) -> wasmtime::Result<webgpu::GpuIntegerCoordinateOut> {
-        todo!()
+        let texture = self.table.get(&texture).unwrap();
+        Ok(texture.descriptor.mip_level_count)
     }
 
     fn sample_count(
         &mut self,
-        _self_: Resource<webgpu::GpuTexture>,
+        texture: Resource<webgpu::GpuTexture>,
     ) -> wasmtime::Result<webgpu::GpuSize32Out> {
-        todo!()
+        let texture = self.table.get(&texture).unwrap();
+        Ok(texture.descriptor.sample_count)
     }
 
     fn dimension(
         &mut self,
-        _self_: Resource<webgpu::GpuTexture>,
+        texture: Resource<webgpu::GpuTexture>,
     ) -> wasmtime::Result<webgpu::GpuTextureDimension> {
-        todo!()
+        let texture = self.table.get(&texture).unwrap();
+        Ok(texture.descriptor.dimension.into())
     }
 
     fn format(
         &mut self,
-        _self_: Resource<webgpu::GpuTexture>,
+        texture: Resource<webgpu::GpuTexture>,
     ) -> wasmtime::Result<webgpu::GpuTextureFormat> {
-        todo!()
+        let texture = self.table.get(&texture).unwrap();
+        Ok(texture.descriptor.format.into())
     }
 
     fn usage(
         &mut self,
-        _self_: Resource<webgpu::GpuTexture>,
+        texture: Resource<webgpu::GpuTexture>,
     ) -> wasmtime::Result<webgpu::GpuFlagsConstant> {
-        todo!()
+        let texture = self.table.get(&texture).unwrap();
+        Ok(texture.descriptor.usage.bits())
     }
 
-    fn label(&mut self, _self_: Resource<webgpu::GpuTexture>) -> wasmtime::Result<String> {
-        todo!()
+    fn label(&mut self, texture: Resource<webgpu::GpuTexture>) -> wasmtime::Result<String> {
+        Ok(self.table.get(&texture).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<webgpu::GpuTexture>,
-        _label: String,
+        texture: Resource<webgpu::GpuTexture>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&texture).unwrap().label = label;
+        Ok(())
     }
 }
 
 impl webgpu::HostGpuTextureView for HostState {
-    fn drop(&mut self, _rep: Resource<wgpu_core::id::TextureViewId>) -> wasmtime::Result<()> {
+    fn drop(&mut self, texture_view: Resource<webgpu::GpuTextureView>) -> wasmtime::Result<()> {
+        let texture_view = self.table.delete(texture_view).unwrap();
+        if let Err(e) = self
+            .instance
+            .texture_view_drop::<crate::Backend>(texture_view.inner, false)
+        {
+            log::error!("webgpu error: {}", Error::from_core(&e).message);
+        }
         Ok(())
     }
 
     fn label(
         &mut self,
-        _self_: Resource<wgpu_core::id::TextureViewId>,
+        texture_view: Resource<webgpu::GpuTextureView>,
     ) -> wasmtime::Result<String> {
-        todo!()
+        Ok(self.table.get(&texture_view).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<wgpu_core::id::TextureViewId>,
-        _label: String,
+        texture_view: Resource<webgpu::GpuTextureView>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&texture_view).unwrap().label = label;
+        Ok(())
     }
 }
 
 impl webgpu::HostGpuCommandBuffer for HostState {
-    fn drop(&mut self, _rep: Resource<webgpu::GpuCommandBuffer>) -> wasmtime::Result<()> {
-        // self.web_gpu_host.command_buffers.remove(&rep.rep());
+    fn drop(&mut self, command_buffer: Resource<webgpu::GpuCommandBuffer>) -> wasmtime::Result<()> {
+        // submitted command buffers were already taken out of the table by submit.
+        let command_buffer = self.table.delete(command_buffer).unwrap();
+        self.instance
+            .command_buffer_drop::<crate::Backend>(command_buffer.inner);
         Ok(())
     }
 
     fn label(
         &mut self,
-        _self_: Resource<wgpu_core::id::CommandBufferId>,
+        command_buffer: Resource<webgpu::GpuCommandBuffer>,
     ) -> wasmtime::Result<String> {
-        todo!()
+        Ok(self.table.get(&command_buffer).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<wgpu_core::id::CommandBufferId>,
-        _label: String,
+        command_buffer: Resource<webgpu::GpuCommandBuffer>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&command_buffer).unwrap().label = label;
+        Ok(())
     }
 }
 
 impl webgpu::HostGpuShaderModule for HostState {
-    fn drop(&mut self, _rep: Resource<webgpu::GpuShaderModule>) -> wasmtime::Result<()> {
-        // self.web_gpu_host.shaders.remove(&rep.rep());
+    fn drop(&mut self, shader_module: Resource<webgpu::GpuShaderModule>) -> wasmtime::Result<()> {
+        let shader_module = self.table.delete(shader_module).unwrap();
+        self.instance
+            .shader_module_drop::<crate::Backend>(shader_module.shader_module);
         Ok(())
     }
 
     fn get_compilation_info(
         &mut self,
-        _self_: Resource<wgpu_core::id::ShaderModuleId>,
+        shader_module: Resource<webgpu::GpuShaderModule>,
     ) -> wasmtime::Result<Resource<webgpu::GpuCompilationInfo>> {
-        todo!()
+        let shader_module = self.table.get(&shader_module).unwrap();
+        let info = CompilationInfo {
+            messages: shader_module.compilation_messages.clone(),
+        };
+        Ok(self.table.push(info).unwrap())
     }
 
     fn label(
         &mut self,
-        _self_: Resource<wgpu_core::id::ShaderModuleId>,
+        shader_module: Resource<webgpu::GpuShaderModule>,
     ) -> wasmtime::Result<String> {
-        todo!()
+        Ok(self.table.get(&shader_module).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<wgpu_core::id::ShaderModuleId>,
-        _label: String,
+        shader_module: Resource<webgpu::GpuShaderModule>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&shader_module).unwrap().label = label;
+        Ok(())
     }
 }
 
 impl webgpu::HostGpuRenderPipeline for HostState {
-    fn drop(&mut self, _rep: Resource<webgpu::GpuRenderPipeline>) -> wasmtime::Result<()> {
-        // TODO:
+    fn drop(
+        &mut self,
+        render_pipeline: Resource<webgpu::GpuRenderPipeline>,
+    ) -> wasmtime::Result<()> {
+        let render_pipeline = self.table.delete(render_pipeline).unwrap();
+        self.instance
+            .render_pipeline_drop::<crate::Backend>(render_pipeline.inner);
         Ok(())
     }
 
     fn label(
         &mut self,
-        _self_: Resource<wgpu_core::id::RenderPipelineId>,
+        render_pipeline: Resource<webgpu::GpuRenderPipeline>,
     ) -> wasmtime::Result<String> {
-        todo!()
+        Ok(self.table.get(&render_pipeline).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<wgpu_core::id::RenderPipelineId>,
-        _label: String,
+        render_pipeline: Resource<webgpu::GpuRenderPipeline>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&render_pipeline).unwrap().label = label;
+        Ok(())
     }
 
     fn get_bind_group_layout(
         &mut self,
-        _self_: Resource<wgpu_core::id::RenderPipelineId>,
-        _index: u32,
+        render_pipeline: Resource<webgpu::GpuRenderPipeline>,
+        index: u32,
     ) -> wasmtime::Result<Resource<webgpu::GpuBindGroupLayout>> {
-        todo!()
+        let render_pipeline = self.table.get(&render_pipeline).unwrap();
+        // wgpu-core returns the id of the layout the pipeline already uses and bumps its ref count, so dropping it like any other bind group layout just releases that reference.
+        let bind_group_layout = self.handle_core_result(
+            &render_pipeline.error_sink,
+            self.instance
+                .render_pipeline_get_bind_group_layout::<crate::Backend>(
+                    render_pipeline.inner,
+                    index,
+                    (),
+                ),
+        );
+        Ok(self
+            .table
+            .push(Labeled::new(bind_group_layout, None))
+            .unwrap())
     }
 }
 
@@ -653,65 +2144,132 @@ impl webgpu::HostGpuAdapter for HostState {
         &mut self,
         adapter: Resource<wgpu_core::id::AdapterId>,
         descriptor: Option<webgpu::GpuDeviceDescriptor>,
-    ) -> wasmtime::Result<Resource<webgpu::GpuDevice>> {
+    ) -> wasmtime::Result<Result<Resource<webgpu::GpuDevice>, String>> {
         let adapter_id = self.table.get(&adapter).unwrap();
+        let (label, queue_label) = descriptor
+            .as_ref()
+            .map(|d| {
+                let queue_label = d.default_queue.as_ref().and_then(|q| q.label.clone());
+                (d.label.clone(), queue_label)
+            })
+            .unwrap_or_default();
+
+        let descriptor = match descriptor.map(|d| d.try_to_core(&self.table)) {
+            Some(Ok(descriptor)) => descriptor,
+            Some(Err(e)) => return Ok(Err(e.to_string())),
+            None => Default::default(),
+        };
 
-        let device_id = core_result(
-            self.instance.adapter_request_device::<crate::Backend>(
-                *adapter_id,
-                &descriptor
-                    .map(|d| d.to_core(&self.table))
-                    .unwrap_or_default(),
-                None,
-                (),
-            ),
-        )
-        .unwrap();
+        // https://www.w3.org/TR/webgpu/#dom-gpuadapter-requestdevice
+        // wgpu-core checks this as well, but only reports the first problem.
+        let adapter_features = self
+            .instance
+            .adapter_features::<crate::Backend>(*adapter_id)
+            .unwrap();
+        let missing_features = descriptor.features - adapter_features;
+        if !missing_features.is_empty() {
+            return Ok(Err(format!(
+                "adapter doesn't support required features {missing_features:?}"
+            )));
+        }
+        let adapter_limits = self
+            .instance
+            .adapter_limits::<crate::Backend>(*adapter_id)
+            .unwrap();
+        let mut exceeded_limits = vec![];
+        descriptor.limits.check_limits_with_fail_fn(
+            &adapter_limits,
+            false,
+            |name, requested, allowed| {
+                exceeded_limits.push(format!(
+                    "{name} (requested {requested}, adapter allows {allowed})"
+                ))
+            },
+        );
+        if !exceeded_limits.is_empty() {
+            return Ok(Err(format!(
+                "required limits exceed the adapter's: {}",
+                exceeded_limits.join(", ")
+            )));
+        }
+
+        let (device_id, error) = self.instance.adapter_request_device::<crate::Backend>(
+            *adapter_id,
+            &descriptor,
+            None,
+            (),
+        );
+        if let Some(error) = error {
+            return Ok(Err(error.to_string()));
+        }
 
         let daq = self
             .table
-            .push_child(
-                Device {
-                    device: device_id,
-                    adapter: *adapter_id,
-                },
-                &adapter,
-            )
+            .push(Device {
+                device: device_id,
+                adapter: *adapter_id,
+                error_sink: ErrorSink::new(device_id),
+                label: label.unwrap_or_default(),
+                queue_label: Arc::new(Mutex::new(queue_label.unwrap_or_default())),
+            })
             .unwrap();
 
-        Ok(daq)
+        Ok(Ok(daq))
     }
 
-    fn drop(&mut self, _adapter: Resource<webgpu::GpuAdapter>) -> wasmtime::Result<()> {
+    fn drop(&mut self, adapter: Resource<webgpu::GpuAdapter>) -> wasmtime::Result<()> {
+        let adapter = self.table.delete(adapter).unwrap();
+        self.instance.adapter_drop::<crate::Backend>(adapter);
         Ok(())
     }
 
     fn features(
         &mut self,
-        _self_: wasmtime::component::Resource<wgpu_core::id::AdapterId>,
+        adapter: wasmtime::component::Resource<wgpu_core::id::AdapterId>,
     ) -> wasmtime::Result<wasmtime::component::Resource<webgpu::GpuSupportedFeatures>> {
-        todo!()
+        let adapter = *self.table.get(&adapter).unwrap();
+        let features = self
+            .instance
+            .adapter_features::<crate::Backend>(adapter)
+            .unwrap();
+        Ok(self.table.push(features).unwrap())
     }
 
     fn limits(
         &mut self,
-        _self_: wasmtime::component::Resource<wgpu_core::id::AdapterId>,
+        adapter: wasmtime::component::Resource<wgpu_core::id::AdapterId>,
     ) -> wasmtime::Result<wasmtime::component::Resource<webgpu::GpuSupportedLimits>> {
-        todo!()
+        let adapter = *self.table.get(&adapter).unwrap();
+        let limits = self
+            .instance
+            .adapter_limits::<crate::Backend>(adapter)
+            .unwrap();
+        Ok(self.table.push(limits).unwrap())
     }
 
     fn is_fallback_adapter(
         &mut self,
-        _self_: wasmtime::component::Resource<wgpu_core::id::AdapterId>,
+        adapter: wasmtime::component::Resource<wgpu_core::id::AdapterId>,
     ) -> wasmtime::Result<bool> {
-        todo!()
+        let adapter = *self.table.get(&adapter).unwrap();
+        let info = self
+            .instance
+            .adapter_get_info::<crate::Backend>(adapter)
+            .unwrap();
+        // wgpu doesn't have a fallback flag, software adapters are the closest thing.
+        Ok(info.device_type == wgpu_types::DeviceType::Cpu)
     }
 
     fn request_adapter_info(
         &mut self,
-        _self_: wasmtime::component::Resource<wgpu_core::id::AdapterId>,
+        adapter: wasmtime::component::Resource<wgpu_core::id::AdapterId>,
     ) -> wasmtime::Result<wasmtime::component::Resource<webgpu::GpuAdapterInfo>> {
-        todo!()
+        let adapter = *self.table.get(&adapter).unwrap();
+        let info = self
+            .instance
+            .adapter_get_info::<crate::Backend>(adapter)
+            .unwrap();
+        Ok(self.table.push(info).unwrap())
     }
 }
 
@@ -723,19 +2281,23 @@ impl webgpu::HostGpuQueue for HostState {
     ) -> wasmtime::Result<()> {
         let command_buffers = val
             .into_iter()
-            .map(|buffer| self.table.delete(buffer).unwrap())
+            .map(|buffer| self.table.delete(buffer).unwrap().inner)
             .collect::<Vec<_>>();
 
         let daq = self.table.get(&daq).unwrap();
-        self.instance
+        if let Err(e) = self
+            .instance
             .queue_submit::<crate::Backend>(daq.device, &command_buffers)
-            .unwrap();
+        {
+            self.handle_error(&daq.error_sink, e);
+        }
 
         Ok(())
     }
 
-    fn drop(&mut self, _rep: Resource<Device>) -> wasmtime::Result<()> {
-        // todo!()
+    fn drop(&mut self, queue: Resource<Device>) -> wasmtime::Result<()> {
+        // the device is released by its own handle.
+        self.table.delete(queue).unwrap();
         Ok(())
     }
 
@@ -754,18 +2316,32 @@ impl webgpu::HostGpuQueue for HostState {
     ) -> wasmtime::Result<()> {
         let queue = self.table.get(&queue).unwrap();
         let buffer = self.table.get(&buffer).unwrap();
-        let mut data = &data[..];
-        if let Some(data_offset) = data_offset {
-            let data_offset = data_offset as usize;
-            data = &data[data_offset..];
-        }
-        if let Some(size) = size {
-            let size = size as usize;
-            data = &data[..size];
+        // https://www.w3.org/TR/webgpu/#dom-gpuqueue-writebuffer
+        let contents = usize::try_from(data_offset.unwrap_or(0))
+            .ok()
+            .and_then(|data_offset| data.get(data_offset..))
+            .and_then(|data| match size {
+                Some(size) => usize::try_from(size).ok().and_then(|size| data.get(..size)),
+                None => Some(data),
+            });
+        let Some(contents) = contents else {
+            self.handle_error(
+                &queue.error_sink,
+                ValidationError(format!(
+                    "write-buffer range is out of bounds for {} bytes of data",
+                    data.len()
+                )),
+            );
+            return Ok(());
+        };
+        if let Err(e) = self.instance.queue_write_buffer::<crate::Backend>(
+            queue.device,
+            buffer.buffer,
+            buffer_offset,
+            contents,
+        ) {
+            self.handle_error(&queue.error_sink, e);
         }
-        self.instance
-            .queue_write_buffer::<crate::Backend>(queue.device, buffer.buffer, buffer_offset, &data)
-            .unwrap();
 
         Ok(())
     }
@@ -779,15 +2355,25 @@ impl webgpu::HostGpuQueue for HostState {
         size: webgpu::GpuExtent3D,
     ) -> wasmtime::Result<()> {
         let device = self.table.get(&device).unwrap();
-        self.instance
-            .queue_write_texture::<crate::Backend>(
-                device.device,
-                &destination.to_core(&self.table),
-                &data,
-                &data_layout.to_core(&self.table),
-                &size.to_core(&self.table),
-            )
-            .unwrap();
+        let (destination, size) = match (
+            destination.try_to_core(&self.table),
+            size.try_to_core(&self.table),
+        ) {
+            (Ok(destination), Ok(size)) => (destination, size),
+            (Err(e), _) | (_, Err(e)) => {
+                self.handle_error(&device.error_sink, e);
+                return Ok(());
+            }
+        };
+        if let Err(e) = self.instance.queue_write_texture::<crate::Backend>(
+            device.device,
+            &destination,
+            &data,
+            &data_layout.to_core(&self.table),
+            &size,
+        ) {
+            self.handle_error(&device.error_sink, e);
+        }
         Ok(())
     }
 
@@ -801,25 +2387,46 @@ impl webgpu::HostGpuQueue for HostState {
         todo!()
     }
 
-    fn label(&mut self, _self_: Resource<Device>) -> wasmtime::Result<String> {
-        todo!()
+    fn label(&mut self, queue: Resource<Device>) -> wasmtime::Result<String> {
+        let queue = self.table.get(&queue).unwrap();
+        let label = queue.queue_label.lock().unwrap().clone();
+        Ok(label)
     }
 
-    fn set_label(&mut self, _self_: Resource<Device>, _label: String) -> wasmtime::Result<()> {
-        todo!()
+    fn set_label(&mut self, queue: Resource<Device>, label: String) -> wasmtime::Result<()> {
+        let queue = self.table.get(&queue).unwrap();
+        *queue.queue_label.lock().unwrap() = label;
+        Ok(())
     }
 }
 
 impl webgpu::HostGpuCommandEncoder for HostState {
     fn begin_render_pass(
         &mut self,
-        command_encoder: Resource<wgpu_core::id::CommandEncoderId>,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
         descriptor: webgpu::GpuRenderPassDescriptor,
     ) -> wasmtime::Result<Resource<webgpu::GpuRenderPassEncoder>> {
+        let command_encoder = self.table.get(&command_encoder).unwrap();
+        let label = descriptor.label.clone();
         // can't use to_core because depth_stencil_attachment is Option<&x>.
         let depth_stencil_attachment = descriptor
             .depth_stencil_attachment
             .map(|d| d.to_core(&self.table));
+        let query_sets = descriptor
+            .timestamp_writes
+            .map(|t| t.try_to_core(&self.table))
+            .transpose()
+            .and_then(|timestamp_writes| {
+                let occlusion_query_set = descriptor
+                    .occlusion_query_set
+                    .map(|q| self.table.get(&q).unwrap().id())
+                    .transpose()?;
+                Ok((timestamp_writes, occlusion_query_set))
+            });
+        let ((timestamp_writes, occlusion_query_set), invalid) = match query_sets {
+            Ok(query_sets) => (query_sets, None),
+            Err(e) => ((None, None), Some(e.to_string())),
+        };
         let descriptor = wgpu_core::command::RenderPassDescriptor {
             label: descriptor.label.map(|l| l.into()),
             color_attachments: descriptor
@@ -829,172 +2436,391 @@ impl webgpu::HostGpuCommandEncoder for HostState {
                 .collect::<Vec<_>>()
                 .into(),
             depth_stencil_attachment: depth_stencil_attachment.as_ref(),
-            // timestamp_writes: self.timestamp_writes,
-            // occlusion_query_set: self.occlusion_query_set,
+            timestamp_writes: timestamp_writes.as_ref(),
+            occlusion_query_set,
             // TODO: self.max_draw_count not used
-            // TODO: remove default
-            ..Default::default()
         };
         let render_pass =
-            wgpu_core::command::RenderPass::new(command_encoder.to_core(&self.table), &descriptor);
+            wgpu_core::command::RenderPass::new(command_encoder.command_encoder, &descriptor);
+
+        let render_pass = RenderPassEncoder {
+            render_pass,
+            error_sink: command_encoder.error_sink.clone(),
+            label: label.unwrap_or_default(),
+            debug_group_depth: 0,
+            invalid,
+        };
 
         Ok(self.table.push(render_pass).unwrap())
     }
 
     fn finish(
         &mut self,
-        command_encoder: Resource<wgpu_core::id::CommandEncoderId>,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
         descriptor: Option<webgpu::GpuCommandBufferDescriptor>,
     ) -> wasmtime::Result<Resource<webgpu::GpuCommandBuffer>> {
         let command_encoder = self.table.delete(command_encoder).unwrap();
-        let command_buffer = core_result(
+        let label = descriptor.as_ref().and_then(|d| d.label.clone());
+        if command_encoder.debug_group_depth != 0 {
+            self.handle_error(
+                &command_encoder.error_sink,
+                unbalanced_debug_groups(command_encoder.debug_group_depth),
+            );
+        }
+        let command_buffer = self.handle_core_result(
+            &command_encoder.error_sink,
             self.instance.command_encoder_finish::<crate::Backend>(
-                command_encoder,
+                command_encoder.command_encoder,
                 &descriptor
                     .map(|d| d.to_core(&self.table))
                     .unwrap_or_default(),
             ),
-        )
-        .unwrap();
-        Ok(self.table.push(command_buffer).unwrap())
+        );
+        Ok(self
+            .table
+            .push(Labeled::new(command_buffer, label))
+            .unwrap())
     }
 
-    fn drop(&mut self, _rep: Resource<wgpu_core::id::CommandEncoderId>) -> wasmtime::Result<()> {
+    fn drop(
+        &mut self,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
+    ) -> wasmtime::Result<()> {
+        // finished command encoders were already taken out of the table by finish.
+        let command_encoder = self.table.delete(command_encoder).unwrap();
+        self.instance
+            .command_encoder_drop::<crate::Backend>(command_encoder.command_encoder);
         Ok(())
     }
 
     fn begin_compute_pass(
         &mut self,
-        _self_: Resource<wgpu_core::id::CommandEncoderId>,
-        _descriptor: Option<webgpu::GpuComputePassDescriptor>,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
+        descriptor: Option<webgpu::GpuComputePassDescriptor>,
     ) -> wasmtime::Result<Resource<webgpu::GpuComputePassEncoder>> {
-        todo!()
+        let command_encoder = self.table.get(&command_encoder).unwrap();
+        let (label, timestamp_writes) = descriptor
+            .map(|d| (d.label, d.timestamp_writes))
+            .unwrap_or_default();
+        let (timestamp_writes, invalid) = match timestamp_writes
+            .map(|t| t.try_to_core(&self.table))
+            .transpose()
+        {
+            Ok(timestamp_writes) => (timestamp_writes, None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        // can't use to_core because timestamp_writes is Option<&x>.
+        let descriptor = wgpu_core::command::ComputePassDescriptor {
+            label: label.as_ref().map(|l| l.into()),
+            timestamp_writes: timestamp_writes.as_ref(),
+        };
+        let compute_pass =
+            wgpu_core::command::ComputePass::new(command_encoder.command_encoder, &descriptor);
+        let compute_pass = ComputePassEncoder {
+            compute_pass,
+            error_sink: command_encoder.error_sink.clone(),
+            label: label.unwrap_or_default(),
+            debug_group_depth: 0,
+            invalid,
+            ended: false,
+        };
+
+        Ok(self.table.push(compute_pass).unwrap())
     }
 
     fn copy_buffer_to_buffer(
         &mut self,
-        _self_: Resource<wgpu_core::id::CommandEncoderId>,
-        _source: Resource<webgpu::GpuBuffer>,
-        _source_offset: webgpu::GpuSize64,
-        _destination: Resource<webgpu::GpuBuffer>,
-        _destination_offset: webgpu::GpuSize64,
-        _size: webgpu::GpuSize64,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
+        source: Resource<webgpu::GpuBuffer>,
+        source_offset: webgpu::GpuSize64,
+        destination: Resource<webgpu::GpuBuffer>,
+        destination_offset: webgpu::GpuSize64,
+        size: webgpu::GpuSize64,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let command_encoder = self.table.get(&command_encoder).unwrap();
+        let source = self.table.get(&source).unwrap().buffer;
+        let destination = self.table.get(&destination).unwrap().buffer;
+        if let Err(e) = self
+            .instance
+            .command_encoder_copy_buffer_to_buffer::<crate::Backend>(
+                command_encoder.command_encoder,
+                source,
+                source_offset,
+                destination,
+                destination_offset,
+                size,
+            )
+        {
+            self.handle_error(&command_encoder.error_sink, e);
+        }
+        Ok(())
     }
 
     fn copy_buffer_to_texture(
         &mut self,
-        _self_: Resource<wgpu_core::id::CommandEncoderId>,
-        _source: webgpu::GpuImageCopyBuffer,
-        _destination: webgpu::GpuImageCopyTexture,
-        _copy_size: webgpu::GpuExtent3D,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
+        source: webgpu::GpuImageCopyBuffer,
+        destination: webgpu::GpuImageCopyTexture,
+        copy_size: webgpu::GpuExtent3D,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let command_encoder = self.table.get(&command_encoder).unwrap();
+        let (source, destination, copy_size) =
+            match image_copy_to_core(&self.table, source, destination, copy_size) {
+                Ok(args) => args,
+                Err(e) => {
+                    self.handle_error(&command_encoder.error_sink, e);
+                    return Ok(());
+                }
+            };
+        if let Err(e) = self
+            .instance
+            .command_encoder_copy_buffer_to_texture::<crate::Backend>(
+                command_encoder.command_encoder,
+                &source,
+                &destination,
+                &copy_size,
+            )
+        {
+            self.handle_error(&command_encoder.error_sink, e);
+        }
+        Ok(())
     }
 
     fn copy_texture_to_buffer(
         &mut self,
-        _self_: Resource<wgpu_core::id::CommandEncoderId>,
-        _source: webgpu::GpuImageCopyTexture,
-        _destination: webgpu::GpuImageCopyBuffer,
-        _copy_size: webgpu::GpuExtent3D,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
+        source: webgpu::GpuImageCopyTexture,
+        destination: webgpu::GpuImageCopyBuffer,
+        copy_size: webgpu::GpuExtent3D,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let command_encoder = self.table.get(&command_encoder).unwrap();
+        let (source, destination, copy_size) =
+            match image_copy_to_core(&self.table, source, destination, copy_size) {
+                Ok(args) => args,
+                Err(e) => {
+                    self.handle_error(&command_encoder.error_sink, e);
+                    return Ok(());
+                }
+            };
+        if let Err(e) = self
+            .instance
+            .command_encoder_copy_texture_to_buffer::<crate::Backend>(
+                command_encoder.command_encoder,
+                &source,
+                &destination,
+                &copy_size,
+            )
+        {
+            self.handle_error(&command_encoder.error_sink, e);
+        }
+        Ok(())
     }
 
     fn copy_texture_to_texture(
         &mut self,
-        _self_: Resource<wgpu_core::id::CommandEncoderId>,
-        _source: webgpu::GpuImageCopyTexture,
-        _destination: webgpu::GpuImageCopyTexture,
-        _copy_size: webgpu::GpuExtent3D,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
+        source: webgpu::GpuImageCopyTexture,
+        destination: webgpu::GpuImageCopyTexture,
+        copy_size: webgpu::GpuExtent3D,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let command_encoder = self.table.get(&command_encoder).unwrap();
+        let (source, destination, copy_size) =
+            match image_copy_to_core(&self.table, source, destination, copy_size) {
+                Ok(args) => args,
+                Err(e) => {
+                    self.handle_error(&command_encoder.error_sink, e);
+                    return Ok(());
+                }
+            };
+        if let Err(e) = self
+            .instance
+            .command_encoder_copy_texture_to_texture::<crate::Backend>(
+                command_encoder.command_encoder,
+                &source,
+                &destination,
+                &copy_size,
+            )
+        {
+            self.handle_error(&command_encoder.error_sink, e);
+        }
+        Ok(())
     }
 
     fn clear_buffer(
         &mut self,
-        _self_: Resource<wgpu_core::id::CommandEncoderId>,
-        _buffer: Resource<webgpu::GpuBuffer>,
-        _offset: Option<webgpu::GpuSize64>,
-        _size: Option<webgpu::GpuSize64>,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
+        buffer: Resource<webgpu::GpuBuffer>,
+        offset: Option<webgpu::GpuSize64>,
+        size: Option<webgpu::GpuSize64>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let command_encoder = self.table.get(&command_encoder).unwrap();
+        let host_buffer = self.table.get(&buffer).unwrap();
+        // https://www.w3.org/TR/webgpu/#dom-gpucommandencoder-clearbuffer
+        let offset = offset.unwrap_or(0);
+        let size = match size {
+            // still has to be a valid clear, there's just nothing to clear.
+            Some(0) => {
+                if let Err(e) = host_buffer.validate_empty_clear(offset) {
+                    self.handle_error(&command_encoder.error_sink, e);
+                }
+                return Ok(());
+            }
+            Some(size) => wgpu_types::BufferSize::new(size),
+            None => None,
+        };
+        let buffer = host_buffer.buffer;
+        if let Err(e) = self
+            .instance
+            .command_encoder_clear_buffer::<crate::Backend>(
+                command_encoder.command_encoder,
+                buffer,
+                offset,
+                size,
+            )
+        {
+            self.handle_error(&command_encoder.error_sink, e);
+        }
+        Ok(())
     }
 
     fn resolve_query_set(
         &mut self,
-        _self_: Resource<wgpu_core::id::CommandEncoderId>,
-        _query_set: Resource<webgpu::GpuQuerySet>,
-        _first_query: webgpu::GpuSize32,
-        _query_count: webgpu::GpuSize32,
-        _destination: Resource<webgpu::GpuBuffer>,
-        _destination_offset: webgpu::GpuSize64,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
+        query_set: Resource<webgpu::GpuQuerySet>,
+        first_query: webgpu::GpuSize32,
+        query_count: webgpu::GpuSize32,
+        destination: Resource<webgpu::GpuBuffer>,
+        destination_offset: webgpu::GpuSize64,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let command_encoder = self.table.get(&command_encoder).unwrap();
+        let query_set = match self.table.get(&query_set).unwrap().id() {
+            Ok(query_set) => query_set,
+            Err(e) => {
+                self.handle_error(&command_encoder.error_sink, e);
+                return Ok(());
+            }
+        };
+        let destination = self.table.get(&destination).unwrap().buffer;
+        if let Err(e) = self
+            .instance
+            .command_encoder_resolve_query_set::<crate::Backend>(
+                command_encoder.command_encoder,
+                query_set,
+                first_query,
+                query_count,
+                destination,
+                destination_offset,
+            )
+        {
+            self.handle_error(&command_encoder.error_sink, e);
+        }
+        Ok(())
     }
 
     fn label(
         &mut self,
-        _self_: Resource<wgpu_core::id::CommandEncoderId>,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
     ) -> wasmtime::Result<String> {
-        todo!()
+        Ok(self.table.get(&command_encoder).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<wgpu_core::id::CommandEncoderId>,
-        _label: String,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&command_encoder).unwrap().label = label;
+        Ok(())
     }
 
     fn push_debug_group(
         &mut self,
-        _self_: Resource<wgpu_core::id::CommandEncoderId>,
-        _group_label: String,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
+        group_label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table
+            .get_mut(&command_encoder)
+            .unwrap()
+            .debug_group_depth += 1;
+        let command_encoder = self.table.get(&command_encoder).unwrap();
+        if let Err(e) = self
+            .instance
+            .command_encoder_push_debug_group::<crate::Backend>(
+                command_encoder.command_encoder,
+                &group_label,
+            )
+        {
+            self.handle_error(&command_encoder.error_sink, e);
+        }
+        Ok(())
     }
 
     fn pop_debug_group(
         &mut self,
-        _self_: Resource<wgpu_core::id::CommandEncoderId>,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let host_encoder = self.table.get_mut(&command_encoder).unwrap();
+        let empty = host_encoder.debug_group_depth == 0;
+        host_encoder.debug_group_depth = host_encoder.debug_group_depth.saturating_sub(1);
+        let command_encoder = self.table.get(&command_encoder).unwrap();
+        // wgpu-core would pass the unbalanced pop straight to the backend.
+        if empty {
+            self.handle_error(&command_encoder.error_sink, pop_empty_debug_group());
+            return Ok(());
+        }
+        if let Err(e) = self
+            .instance
+            .command_encoder_pop_debug_group::<crate::Backend>(command_encoder.command_encoder)
+        {
+            self.handle_error(&command_encoder.error_sink, e);
+        }
+        Ok(())
     }
 
     fn insert_debug_marker(
         &mut self,
-        _self_: Resource<wgpu_core::id::CommandEncoderId>,
-        _marker_label: String,
+        command_encoder: Resource<webgpu::GpuCommandEncoder>,
+        marker_label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let command_encoder = self.table.get(&command_encoder).unwrap();
+        if let Err(e) = self
+            .instance
+            .command_encoder_insert_debug_marker::<crate::Backend>(
+                command_encoder.command_encoder,
+                &marker_label,
+            )
+        {
+            self.handle_error(&command_encoder.error_sink, e);
+        }
+        Ok(())
     }
 }
 
 impl webgpu::HostGpuRenderPassEncoder for HostState {
     fn set_pipeline(
         &mut self,
-        render_pass: Resource<wgpu_core::command::RenderPass>,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
         pipeline: Resource<webgpu::GpuRenderPipeline>,
     ) -> wasmtime::Result<()> {
         let pipeline = pipeline.to_core(&self.table);
-        let render_pass = self.table.get_mut(&render_pass).unwrap();
+        let Some(render_pass) = self.table.get_mut(&render_pass).unwrap().pass() else {
+            return Ok(());
+        };
         wgpu_core::command::render_ffi::wgpu_render_pass_set_pipeline(render_pass, pipeline);
         Ok(())
     }
 
     fn draw(
         &mut self,
-        cwr: Resource<wgpu_core::command::RenderPass>,
+        cwr: Resource<webgpu::GpuRenderPassEncoder>,
         vertex_count: webgpu::GpuSize32,
         instance_count: webgpu::GpuSize32,
         first_vertex: webgpu::GpuSize32,
         first_instance: webgpu::GpuSize32,
     ) -> wasmtime::Result<()> {
-        let cwr = self.table.get_mut(&cwr).unwrap();
+        let Some(cwr) = self.table.get_mut(&cwr).unwrap().pass() else {
+            return Ok(());
+        };
 
         wgpu_core::command::render_ffi::wgpu_render_pass_draw(
             cwr,
@@ -1009,138 +2835,256 @@ impl webgpu::HostGpuRenderPassEncoder for HostState {
 
     fn end(
         &mut self,
-        rpass: Resource<wgpu_core::command::RenderPass>,
-        non_standard_encoder: Resource<wgpu_core::id::CommandEncoderId>,
+        rpass: Resource<webgpu::GpuRenderPassEncoder>,
+        non_standard_encoder: Resource<webgpu::GpuCommandEncoder>,
     ) -> wasmtime::Result<()> {
         let rpass = self.table.delete(rpass).unwrap();
         let encoder = self.table.get(&non_standard_encoder).unwrap();
-        self.instance
-            .command_encoder_run_render_pass::<crate::Backend>(*encoder, &rpass)
-            .unwrap();
+        // https://www.w3.org/TR/webgpu/#dom-gpurenderpassencoder-end
+        // an invalid pass is dropped without running any of its commands.
+        if let Some(message) = rpass.invalid {
+            self.handle_error(&rpass.error_sink, ValidationError(message));
+            return Ok(());
+        }
+        if rpass.debug_group_depth != 0 {
+            self.handle_error(
+                &rpass.error_sink,
+                unbalanced_debug_groups(rpass.debug_group_depth),
+            );
+        }
+        if let Err(e) = self
+            .instance
+            .command_encoder_run_render_pass::<crate::Backend>(
+                encoder.command_encoder,
+                &rpass.render_pass,
+            )
+        {
+            self.handle_error(&rpass.error_sink, e);
+        }
         Ok(())
     }
 
-    fn drop(&mut self, cwr: Resource<wgpu_core::command::RenderPass>) -> wasmtime::Result<()> {
+    fn drop(&mut self, cwr: Resource<webgpu::GpuRenderPassEncoder>) -> wasmtime::Result<()> {
         self.table.delete(cwr).unwrap();
         Ok(())
     }
 
     fn set_viewport(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
-        _x: f32,
-        _y: f32,
-        _width: f32,
-        _height: f32,
-        _min_depth: f32,
-        _max_depth: f32,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        min_depth: f32,
+        max_depth: f32,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let Some(render_pass) = self.table.get_mut(&render_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        wgpu_core::command::render_ffi::wgpu_render_pass_set_viewport(
+            render_pass,
+            x,
+            y,
+            width,
+            height,
+            min_depth,
+            max_depth,
+        );
+        Ok(())
     }
 
     fn set_scissor_rect(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
-        _x: webgpu::GpuIntegerCoordinate,
-        _y: webgpu::GpuIntegerCoordinate,
-        _width: webgpu::GpuIntegerCoordinate,
-        _height: webgpu::GpuIntegerCoordinate,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
+        x: webgpu::GpuIntegerCoordinate,
+        y: webgpu::GpuIntegerCoordinate,
+        width: webgpu::GpuIntegerCoordinate,
+        height: webgpu::GpuIntegerCoordinate,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let Some(render_pass) = self.table.get_mut(&render_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        wgpu_core::command::render_ffi::wgpu_render_pass_set_scissor_rect(
+            render_pass,
+            x,
+            y,
+            width,
+            height,
+        );
+        Ok(())
     }
 
     fn set_blend_constant(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
-        _color: webgpu::GpuColor,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
+        color: webgpu::GpuColor,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let Some(render_pass) = self.table.get_mut(&render_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        wgpu_core::command::render_ffi::wgpu_render_pass_set_blend_constant(
+            render_pass,
+            &color.into(),
+        );
+        Ok(())
     }
 
     fn set_stencil_reference(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
-        _reference: webgpu::GpuStencilValue,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
+        reference: webgpu::GpuStencilValue,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let Some(render_pass) = self.table.get_mut(&render_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        wgpu_core::command::render_ffi::wgpu_render_pass_set_stencil_reference(
+            render_pass,
+            reference,
+        );
+        Ok(())
     }
 
     fn begin_occlusion_query(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
-        _query_index: webgpu::GpuSize32,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
+        query_index: webgpu::GpuSize32,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let Some(render_pass) = self.table.get_mut(&render_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        wgpu_core::command::render_ffi::wgpu_render_pass_begin_occlusion_query(
+            render_pass,
+            query_index,
+        );
+        Ok(())
     }
 
     fn end_occlusion_query(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let Some(render_pass) = self.table.get_mut(&render_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        wgpu_core::command::render_ffi::wgpu_render_pass_end_occlusion_query(render_pass);
+        Ok(())
     }
 
     fn execute_bundles(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
-        _bundles: Vec<Resource<webgpu::GpuRenderBundle>>,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
+        bundles: Vec<Resource<webgpu::GpuRenderBundle>>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let bundles = bundles
+            .into_iter()
+            .map(|b| b.to_core(&self.table))
+            .collect::<Vec<_>>();
+        let Some(render_pass) = self.table.get_mut(&render_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        unsafe {
+            wgpu_core::command::render_ffi::wgpu_render_pass_execute_bundles(
+                render_pass,
+                bundles.as_ptr(),
+                bundles.len(),
+            )
+        };
+        Ok(())
     }
 
     fn label(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
     ) -> wasmtime::Result<String> {
-        todo!()
+        Ok(self.table.get(&render_pass).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
-        _label: String,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&render_pass).unwrap().label = label;
+        Ok(())
     }
 
     fn push_debug_group(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
-        _group_label: String,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
+        group_label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let host_pass = self.table.get_mut(&render_pass).unwrap();
+        host_pass.debug_group_depth += 1;
+        let Some(render_pass) = host_pass.pass() else {
+            return Ok(());
+        };
+        let group_label = debug_label(group_label);
+        // TODO: validate safety.
+        unsafe {
+            wgpu_core::command::render_ffi::wgpu_render_pass_push_debug_group(
+                render_pass,
+                group_label.as_ptr(),
+                0,
+            )
+        };
+        Ok(())
     }
 
     fn pop_debug_group(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let host_pass = self.table.get_mut(&render_pass).unwrap();
+        // wgpu-core would only notice when the pass ends, so we check here like the other encoders do.
+        if host_pass.debug_group_depth == 0 {
+            host_pass.invalidate(pop_empty_debug_group());
+            return Ok(());
+        }
+        host_pass.debug_group_depth -= 1;
+        if let Some(render_pass) = host_pass.pass() {
+            wgpu_core::command::render_ffi::wgpu_render_pass_pop_debug_group(render_pass);
+        }
+        Ok(())
     }
 
     fn insert_debug_marker(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
-        _marker_label: String,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
+        marker_label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let Some(render_pass) = self.table.get_mut(&render_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        let marker_label = debug_label(marker_label);
+        // TODO: validate safety.
+        unsafe {
+            wgpu_core::command::render_ffi::wgpu_render_pass_insert_debug_marker(
+                render_pass,
+                marker_label.as_ptr(),
+                0,
+            )
+        };
+        Ok(())
     }
 
     fn set_bind_group(
         &mut self,
-        render_pass: Resource<wgpu_core::command::RenderPass>,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
         index: webgpu::GpuIndex32,
         bind_group: Resource<webgpu::GpuBindGroup>,
         dynamic_offsets: Option<Vec<webgpu::GpuBufferDynamicOffset>>,
     ) -> wasmtime::Result<()> {
-        let bind_group = *self.table.get(&bind_group).unwrap();
-        let mut render_pass = self.table.get_mut(&render_pass).unwrap();
+        let bind_group = self.table.get(&bind_group).unwrap().inner;
+        let Some(render_pass) = self.table.get_mut(&render_pass).unwrap().pass() else {
+            return Ok(());
+        };
 
-        let dynamic_offsets = dynamic_offsets.unwrap();
+        let dynamic_offsets = dynamic_offsets.unwrap_or_default();
         // TODO: validate safety.
         unsafe {
             wgpu_core::command::render_ffi::wgpu_render_pass_set_bind_group(
-                &mut render_pass,
+                render_pass,
                 index,
                 bind_group,
                 // TODO: Not sure that these are correct. Verify please.
@@ -1154,32 +3098,57 @@ impl webgpu::HostGpuRenderPassEncoder for HostState {
 
     fn set_index_buffer(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
-        _buffer: Resource<webgpu::GpuBuffer>,
-        _index_format: webgpu::GpuIndexFormat,
-        _offset: webgpu::GpuSize64,
-        _size: webgpu::GpuSize64,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
+        buffer: Resource<webgpu::GpuBuffer>,
+        index_format: webgpu::GpuIndexFormat,
+        offset: Option<webgpu::GpuSize64>,
+        size: Option<webgpu::GpuSize64>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let buffer = self.table.get(&buffer).unwrap().buffer;
+        let host_pass = self.table.get_mut(&render_pass).unwrap();
+        // https://www.w3.org/TR/webgpu/#dom-gpurendercommandsmixin-setindexbuffer
+        // wgpu-core uses None for "rest of the buffer", same as a missing size.
+        let size = match size.map(buffer_binding_size).transpose() {
+            Ok(size) => size,
+            Err(e) => {
+                host_pass.invalidate(e);
+                return Ok(());
+            }
+        };
+        let Some(render_pass) = host_pass.pass() else {
+            return Ok(());
+        };
+        render_pass.set_index_buffer(buffer, index_format.into(), offset.unwrap_or(0), size);
+        Ok(())
     }
 
     fn set_vertex_buffer(
         &mut self,
-        render_pass: Resource<wgpu_core::command::RenderPass>,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
         slot: webgpu::GpuIndex32,
         buffer: Resource<webgpu::GpuBuffer>,
         offset: webgpu::GpuSize64,
         size: webgpu::GpuSize64,
     ) -> wasmtime::Result<()> {
         let buffer_id = self.table.get(&buffer).unwrap().buffer;
-        let mut render_pass = self.table.get_mut(&render_pass).unwrap();
+        let host_pass = self.table.get_mut(&render_pass).unwrap();
+        let size = match buffer_binding_size(size) {
+            Ok(size) => size,
+            Err(e) => {
+                host_pass.invalidate(e);
+                return Ok(());
+            }
+        };
+        let Some(render_pass) = host_pass.pass() else {
+            return Ok(());
+        };
 
         wgpu_core::command::render_ffi::wgpu_render_pass_set_vertex_buffer(
-            &mut render_pass,
+            render_pass,
             slot,
             buffer_id,
             offset,
-            Some(size.try_into().unwrap()),
+            Some(size),
         );
 
         Ok(())
@@ -1187,32 +3156,61 @@ impl webgpu::HostGpuRenderPassEncoder for HostState {
 
     fn draw_indexed(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
-        _index_count: webgpu::GpuSize32,
-        _instance_count: webgpu::GpuSize32,
-        _first_index: webgpu::GpuSize32,
-        _base_vertex: webgpu::GpuSignedOffset32,
-        _first_instance: webgpu::GpuSize32,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
+        index_count: webgpu::GpuSize32,
+        instance_count: webgpu::GpuSize32,
+        first_index: webgpu::GpuSize32,
+        base_vertex: webgpu::GpuSignedOffset32,
+        first_instance: webgpu::GpuSize32,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let Some(render_pass) = self.table.get_mut(&render_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        wgpu_core::command::render_ffi::wgpu_render_pass_draw_indexed(
+            render_pass,
+            index_count,
+            instance_count,
+            first_index,
+            base_vertex,
+            first_instance,
+        );
+        Ok(())
     }
 
     fn draw_indirect(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
-        _indirect_buffer: Resource<webgpu::GpuBuffer>,
-        _indirect_offset: webgpu::GpuSize64,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
+        indirect_buffer: Resource<webgpu::GpuBuffer>,
+        indirect_offset: webgpu::GpuSize64,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let indirect_buffer = self.table.get(&indirect_buffer).unwrap().buffer;
+        let Some(render_pass) = self.table.get_mut(&render_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        wgpu_core::command::render_ffi::wgpu_render_pass_draw_indirect(
+            render_pass,
+            indirect_buffer,
+            indirect_offset,
+        );
+        Ok(())
     }
 
     fn draw_indexed_indirect(
         &mut self,
-        _self_: Resource<wgpu_core::command::RenderPass>,
-        _indirect_buffer: Resource<webgpu::GpuBuffer>,
-        _indirect_offset: webgpu::GpuSize64,
+        render_pass: Resource<webgpu::GpuRenderPassEncoder>,
+        indirect_buffer: Resource<webgpu::GpuBuffer>,
+        indirect_offset: webgpu::GpuSize64,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let indirect_buffer = self.table.get(&indirect_buffer).unwrap().buffer;
+        let Some(render_pass) = self.table.get_mut(&render_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        wgpu_core::command::render_ffi::wgpu_render_pass_draw_indexed_indirect(
+            render_pass,
+            indirect_buffer,
+            indirect_offset,
+        );
+        Ok(())
     }
 }
 
@@ -1220,90 +3218,167 @@ impl webgpu::HostGpuUncapturedErrorEvent for HostState {
     fn new(
         &mut self,
         _type_: String,
-        _gpu_uncaptured_error_event_init_dict: webgpu::GpuUncapturedErrorEventInit,
+        gpu_uncaptured_error_event_init_dict: webgpu::GpuUncapturedErrorEventInit,
     ) -> wasmtime::Result<Resource<webgpu::GpuUncapturedErrorEvent>> {
-        todo!()
+        let error = self
+            .table
+            .delete(gpu_uncaptured_error_event_init_dict.error)
+            .unwrap();
+        Ok(self.table.push(UncapturedErrorEvent { error }).unwrap())
     }
 
     fn error(
         &mut self,
-        _self_: Resource<webgpu::GpuUncapturedErrorEvent>,
+        event: Resource<webgpu::GpuUncapturedErrorEvent>,
     ) -> wasmtime::Result<Resource<webgpu::GpuError>> {
-        todo!()
+        let error = self.table.get(&event).unwrap().error.clone();
+        Ok(self.table.push(error).unwrap())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuUncapturedErrorEvent>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(&mut self, event: Resource<webgpu::GpuUncapturedErrorEvent>) -> wasmtime::Result<()> {
+        self.table.delete(event).unwrap();
+        Ok(())
+    }
+}
+impl webgpu::HostGpuUncapturedErrorListener for HostState {
+    fn subscribe(
+        &mut self,
+        listener: Resource<webgpu::GpuUncapturedErrorListener>,
+    ) -> wasmtime::Result<Resource<webgpu::Pollable>> {
+        Ok(preview2::subscribe(self.table_mut(), listener).unwrap())
+    }
+
+    fn get(
+        &mut self,
+        listener: Resource<webgpu::GpuUncapturedErrorListener>,
+    ) -> wasmtime::Result<Option<Resource<webgpu::GpuUncapturedErrorEvent>>> {
+        let listener = self.table.get(&listener).unwrap();
+        let error = listener.data.lock().unwrap().take();
+        Ok(error.map(|error| self.table.push(UncapturedErrorEvent { error }).unwrap()))
+    }
+
+    fn drop(
+        &mut self,
+        listener: Resource<webgpu::GpuUncapturedErrorListener>,
+    ) -> wasmtime::Result<()> {
+        self.table.delete(listener).unwrap();
+        Ok(())
     }
 }
 impl webgpu::HostGpuInternalError for HostState {
-    fn new(&mut self, _message: String) -> wasmtime::Result<Resource<webgpu::GpuInternalError>> {
-        todo!()
+    fn new(&mut self, message: String) -> wasmtime::Result<Resource<webgpu::GpuInternalError>> {
+        let error = Error {
+            kind: webgpu::GpuErrorFilter::Internal,
+            message,
+        };
+        Ok(self.table.push(error).unwrap())
     }
 
-    fn message(&mut self, _self_: Resource<webgpu::GpuInternalError>) -> wasmtime::Result<String> {
-        todo!()
+    fn message(&mut self, error: Resource<webgpu::GpuInternalError>) -> wasmtime::Result<String> {
+        Ok(self.table.get(&error).unwrap().message.clone())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuInternalError>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(&mut self, error: Resource<webgpu::GpuInternalError>) -> wasmtime::Result<()> {
+        self.table.delete(error).unwrap();
+        Ok(())
     }
 }
 impl webgpu::HostGpuOutOfMemoryError for HostState {
-    fn new(&mut self, _message: String) -> wasmtime::Result<Resource<webgpu::GpuOutOfMemoryError>> {
-        todo!()
+    fn new(&mut self, message: String) -> wasmtime::Result<Resource<webgpu::GpuOutOfMemoryError>> {
+        let error = Error {
+            kind: webgpu::GpuErrorFilter::OutOfMemory,
+            message,
+        };
+        Ok(self.table.push(error).unwrap())
     }
 
     fn message(
         &mut self,
-        _self_: Resource<webgpu::GpuOutOfMemoryError>,
+        error: Resource<webgpu::GpuOutOfMemoryError>,
     ) -> wasmtime::Result<String> {
-        todo!()
+        Ok(self.table.get(&error).unwrap().message.clone())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuOutOfMemoryError>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(&mut self, error: Resource<webgpu::GpuOutOfMemoryError>) -> wasmtime::Result<()> {
+        self.table.delete(error).unwrap();
+        Ok(())
     }
 }
 impl webgpu::HostGpuValidationError for HostState {
-    fn new(&mut self, _message: String) -> wasmtime::Result<Resource<webgpu::GpuValidationError>> {
-        todo!()
+    fn new(&mut self, message: String) -> wasmtime::Result<Resource<webgpu::GpuValidationError>> {
+        let error = Error {
+            kind: webgpu::GpuErrorFilter::Validation,
+            message,
+        };
+        Ok(self.table.push(error).unwrap())
     }
 
-    fn message(
-        &mut self,
-        _self_: Resource<webgpu::GpuValidationError>,
-    ) -> wasmtime::Result<String> {
-        todo!()
+    fn message(&mut self, error: Resource<webgpu::GpuValidationError>) -> wasmtime::Result<String> {
+        Ok(self.table.get(&error).unwrap().message.clone())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuValidationError>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(&mut self, error: Resource<webgpu::GpuValidationError>) -> wasmtime::Result<()> {
+        self.table.delete(error).unwrap();
+        Ok(())
     }
 }
 impl webgpu::HostGpuError for HostState {
-    fn message(&mut self, _self_: Resource<webgpu::GpuError>) -> wasmtime::Result<String> {
-        todo!()
+    fn message(&mut self, error: Resource<webgpu::GpuError>) -> wasmtime::Result<String> {
+        Ok(self.table.get(&error).unwrap().message.clone())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuError>) -> wasmtime::Result<()> {
-        todo!()
+    fn kind(
+        &mut self,
+        error: Resource<webgpu::GpuError>,
+    ) -> wasmtime::Result<webgpu::GpuErrorFilter> {
+        Ok(self.table.get(&error).unwrap().kind)
+    }
+
+    fn drop(&mut self, error: Resource<webgpu::GpuError>) -> wasmtime::Result<()> {
+        self.table.delete(error).unwrap();
+        Ok(())
     }
 }
 impl webgpu::HostGpuDeviceLostInfo for HostState {
     fn reason(
         &mut self,
-        _self_: Resource<webgpu::GpuDeviceLostInfo>,
+        lost_info: Resource<webgpu::GpuDeviceLostInfo>,
     ) -> wasmtime::Result<webgpu::GpuDeviceLostReason> {
-        todo!()
+        Ok(self.table.get(&lost_info).unwrap().reason)
     }
 
-    fn message(&mut self, _self_: Resource<webgpu::GpuDeviceLostInfo>) -> wasmtime::Result<String> {
-        todo!()
+    fn message(
+        &mut self,
+        lost_info: Resource<webgpu::GpuDeviceLostInfo>,
+    ) -> wasmtime::Result<String> {
+        Ok(self.table.get(&lost_info).unwrap().message.clone())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuDeviceLostInfo>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(&mut self, lost_info: Resource<webgpu::GpuDeviceLostInfo>) -> wasmtime::Result<()> {
+        self.table.delete(lost_info).unwrap();
+        Ok(())
+    }
+}
+impl webgpu::HostGpuDeviceLostFuture for HostState {
+    fn subscribe(
+        &mut self,
+        lost_future: Resource<webgpu::GpuDeviceLostFuture>,
+    ) -> wasmtime::Result<Resource<webgpu::Pollable>> {
+        Ok(preview2::subscribe(self.table_mut(), lost_future).unwrap())
+    }
+
+    fn get(
+        &mut self,
+        lost_future: Resource<webgpu::GpuDeviceLostFuture>,
+    ) -> wasmtime::Result<Option<Resource<webgpu::GpuDeviceLostInfo>>> {
+        let lost_future = self.table.get(&lost_future).unwrap();
+        let lost_info = lost_future.error_sink.lost.lock().unwrap().clone();
+        Ok(lost_info.map(|lost_info| self.table.push(lost_info).unwrap()))
+    }
+
+    fn drop(&mut self, lost_future: Resource<webgpu::GpuDeviceLostFuture>) -> wasmtime::Result<()> {
+        self.table.delete(lost_future).unwrap();
+        Ok(())
     }
 }
 impl webgpu::HostGpuCanvasContext for HostState {
@@ -1338,413 +3413,871 @@ impl webgpu::HostGpuCanvasContext for HostState {
     }
 }
 impl webgpu::HostGpuRenderBundle for HostState {
-    fn label(&mut self, _self_: Resource<webgpu::GpuRenderBundle>) -> wasmtime::Result<String> {
-        todo!()
+    fn label(
+        &mut self,
+        render_bundle: Resource<webgpu::GpuRenderBundle>,
+    ) -> wasmtime::Result<String> {
+        Ok(self.table.get(&render_bundle).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundle>,
-        _label: String,
+        render_bundle: Resource<webgpu::GpuRenderBundle>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&render_bundle).unwrap().label = label;
+        Ok(())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuRenderBundle>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(&mut self, render_bundle: Resource<webgpu::GpuRenderBundle>) -> wasmtime::Result<()> {
+        let render_bundle = self.table.delete(render_bundle).unwrap();
+        self.instance
+            .render_bundle_drop::<crate::Backend>(render_bundle.inner);
+        Ok(())
     }
 }
 impl webgpu::HostGpuComputePassEncoder for HostState {
     fn set_pipeline(
         &mut self,
-        _self_: Resource<webgpu::GpuComputePassEncoder>,
-        _pipeline: Resource<webgpu::GpuComputePipeline>,
+        compute_pass: Resource<webgpu::GpuComputePassEncoder>,
+        pipeline: Resource<webgpu::GpuComputePipeline>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let pipeline = pipeline.to_core(&self.table);
+        let Some(compute_pass) = self.table.get_mut(&compute_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        wgpu_core::command::compute_ffi::wgpu_compute_pass_set_pipeline(compute_pass, pipeline);
+        Ok(())
     }
 
     fn dispatch_workgroups(
         &mut self,
-        _self_: Resource<webgpu::GpuComputePassEncoder>,
-        _workgroup_count_x: webgpu::GpuSize32,
-        _workgroup_count_y: Option<webgpu::GpuSize32>,
-        _workgroup_count_z: Option<webgpu::GpuSize32>,
+        compute_pass: Resource<webgpu::GpuComputePassEncoder>,
+        workgroup_count_x: webgpu::GpuSize32,
+        workgroup_count_y: Option<webgpu::GpuSize32>,
+        workgroup_count_z: Option<webgpu::GpuSize32>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let Some(compute_pass) = self.table.get_mut(&compute_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        // https://www.w3.org/TR/webgpu/#dom-gpucomputepassencoder-dispatchworkgroups
+        wgpu_core::command::compute_ffi::wgpu_compute_pass_dispatch_workgroups(
+            compute_pass,
+            workgroup_count_x,
+            workgroup_count_y.unwrap_or(1),
+            workgroup_count_z.unwrap_or(1),
+        );
+        Ok(())
     }
 
     fn dispatch_workgroups_indirect(
         &mut self,
-        _self_: Resource<webgpu::GpuComputePassEncoder>,
-        _indirect_buffer: Resource<webgpu::GpuBuffer>,
-        _indirect_offset: webgpu::GpuSize64,
+        compute_pass: Resource<webgpu::GpuComputePassEncoder>,
+        indirect_buffer: Resource<webgpu::GpuBuffer>,
+        indirect_offset: webgpu::GpuSize64,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let indirect_buffer = self.table.get(&indirect_buffer).unwrap().buffer;
+        let Some(compute_pass) = self.table.get_mut(&compute_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        wgpu_core::command::compute_ffi::wgpu_compute_pass_dispatch_workgroups_indirect(
+            compute_pass,
+            indirect_buffer,
+            indirect_offset,
+        );
+        Ok(())
     }
 
-    fn end(&mut self, _self_: Resource<webgpu::GpuComputePassEncoder>) -> wasmtime::Result<()> {
-        todo!()
+    fn end(
+        &mut self,
+        compute_pass: Resource<webgpu::GpuComputePassEncoder>,
+    ) -> wasmtime::Result<()> {
+        // Unlike render passes, compute passes know which encoder they belong to.
+        let host_pass = self.table.get_mut(&compute_pass).unwrap();
+        // https://www.w3.org/TR/webgpu/#dom-gpucomputepassencoder-end
+        if std::mem::replace(&mut host_pass.ended, true) {
+            let compute_pass = self.table.get(&compute_pass).unwrap();
+            self.handle_error(
+                &compute_pass.error_sink,
+                ValidationError("end called on a compute pass that has already ended".to_string()),
+            );
+            return Ok(());
+        }
+        let compute_pass = self.table.get(&compute_pass).unwrap();
+        // an invalid pass is dropped without running any of its commands.
+        if let Some(message) = &compute_pass.invalid {
+            self.handle_error(&compute_pass.error_sink, ValidationError(message.clone()));
+            return Ok(());
+        }
+        if compute_pass.debug_group_depth != 0 {
+            self.handle_error(
+                &compute_pass.error_sink,
+                unbalanced_debug_groups(compute_pass.debug_group_depth),
+            );
+        }
+        if let Err(e) = self
+            .instance
+            .command_encoder_run_compute_pass::<crate::Backend>(
+                compute_pass.compute_pass.parent_id(),
+                &compute_pass.compute_pass,
+            )
+        {
+            self.handle_error(&compute_pass.error_sink, e);
+        }
+        Ok(())
     }
 
     fn label(
         &mut self,
-        _self_: Resource<webgpu::GpuComputePassEncoder>,
+        compute_pass: Resource<webgpu::GpuComputePassEncoder>,
     ) -> wasmtime::Result<String> {
-        todo!()
+        Ok(self.table.get(&compute_pass).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<webgpu::GpuComputePassEncoder>,
-        _label: String,
+        compute_pass: Resource<webgpu::GpuComputePassEncoder>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&compute_pass).unwrap().label = label;
+        Ok(())
     }
 
     fn push_debug_group(
         &mut self,
-        _self_: Resource<webgpu::GpuComputePassEncoder>,
-        _group_label: String,
+        compute_pass: Resource<webgpu::GpuComputePassEncoder>,
+        group_label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let host_pass = self.table.get_mut(&compute_pass).unwrap();
+        host_pass.debug_group_depth += 1;
+        let Some(compute_pass) = host_pass.pass() else {
+            return Ok(());
+        };
+        let group_label = debug_label(group_label);
+        // TODO: validate safety.
+        unsafe {
+            wgpu_core::command::compute_ffi::wgpu_compute_pass_push_debug_group(
+                compute_pass,
+                group_label.as_ptr(),
+                0,
+            )
+        };
+        Ok(())
     }
 
     fn pop_debug_group(
         &mut self,
-        _self_: Resource<webgpu::GpuComputePassEncoder>,
+        compute_pass: Resource<webgpu::GpuComputePassEncoder>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let host_pass = self.table.get_mut(&compute_pass).unwrap();
+        // wgpu-core would only notice when the pass ends, so we check here like the other encoders do.
+        if host_pass.debug_group_depth == 0 {
+            host_pass.invalidate(pop_empty_debug_group());
+            return Ok(());
+        }
+        host_pass.debug_group_depth -= 1;
+        if let Some(compute_pass) = host_pass.pass() {
+            wgpu_core::command::compute_ffi::wgpu_compute_pass_pop_debug_group(compute_pass);
+        }
+        Ok(())
     }
 
     fn insert_debug_marker(
         &mut self,
-        _self_: Resource<webgpu::GpuComputePassEncoder>,
-        _marker_label: String,
+        compute_pass: Resource<webgpu::GpuComputePassEncoder>,
+        marker_label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let Some(compute_pass) = self.table.get_mut(&compute_pass).unwrap().pass() else {
+            return Ok(());
+        };
+        let marker_label = debug_label(marker_label);
+        // TODO: validate safety.
+        unsafe {
+            wgpu_core::command::compute_ffi::wgpu_compute_pass_insert_debug_marker(
+                compute_pass,
+                marker_label.as_ptr(),
+                0,
+            )
+        };
+        Ok(())
     }
 
     fn set_bind_group(
         &mut self,
-        _self_: Resource<webgpu::GpuComputePassEncoder>,
-        _index: webgpu::GpuIndex32,
-        _bind_group: Resource<webgpu::GpuBindGroup>,
-        _dynamic_offsets: Option<Vec<webgpu::GpuBufferDynamicOffset>>,
+        compute_pass: Resource<webgpu::GpuComputePassEncoder>,
+        index: webgpu::GpuIndex32,
+        bind_group: Resource<webgpu::GpuBindGroup>,
+        dynamic_offsets: Option<Vec<webgpu::GpuBufferDynamicOffset>>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let bind_group = self.table.get(&bind_group).unwrap().inner;
+        let Some(compute_pass) = self.table.get_mut(&compute_pass).unwrap().pass() else {
+            return Ok(());
+        };
+
+        let dynamic_offsets = dynamic_offsets.unwrap_or_default();
+        // TODO: validate safety.
+        unsafe {
+            wgpu_core::command::compute_ffi::wgpu_compute_pass_set_bind_group(
+                compute_pass,
+                index,
+                bind_group,
+                dynamic_offsets.as_ptr(),
+                dynamic_offsets.len(),
+            )
+        };
+
+        Ok(())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuComputePassEncoder>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(
+        &mut self,
+        compute_pass: Resource<webgpu::GpuComputePassEncoder>,
+    ) -> wasmtime::Result<()> {
+        self.table.delete(compute_pass).unwrap();
+        Ok(())
     }
 }
 impl webgpu::HostGpuPipelineError for HostState {
     fn new(
         &mut self,
-        _message: Option<String>,
-        _options: webgpu::GpuPipelineErrorInit,
+        message: Option<String>,
+        options: webgpu::GpuPipelineErrorInit,
     ) -> wasmtime::Result<Resource<webgpu::GpuPipelineError>> {
-        todo!()
+        let error = PipelineError {
+            reason: options.reason,
+            message: message.unwrap_or_default(),
+        };
+        Ok(self.table.push(error).unwrap())
+    }
+
+    fn message(&mut self, error: Resource<webgpu::GpuPipelineError>) -> wasmtime::Result<String> {
+        let error = self.table.get(&error).unwrap();
+        Ok(error.message.clone())
     }
 
     fn reason(
         &mut self,
-        _self_: Resource<webgpu::GpuPipelineError>,
+        error: Resource<webgpu::GpuPipelineError>,
     ) -> wasmtime::Result<webgpu::GpuPipelineErrorReason> {
-        todo!()
+        let error = self.table.get(&error).unwrap();
+        Ok(error.reason)
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuPipelineError>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(&mut self, error: Resource<webgpu::GpuPipelineError>) -> wasmtime::Result<()> {
+        self.table.delete(error).unwrap();
+        Ok(())
+    }
+}
+impl webgpu::HostGpuComputePipelineFuture for HostState {
+    fn subscribe(
+        &mut self,
+        pipeline_future: Resource<webgpu::GpuComputePipelineFuture>,
+    ) -> wasmtime::Result<Resource<webgpu::Pollable>> {
+        Ok(preview2::subscribe(self.table_mut(), pipeline_future).unwrap())
+    }
+
+    fn get(
+        &mut self,
+        pipeline_future: Resource<webgpu::GpuComputePipelineFuture>,
+    ) -> wasmtime::Result<
+        Option<Result<Resource<webgpu::GpuComputePipeline>, Resource<webgpu::GpuPipelineError>>>,
+    > {
+        let pipeline_future = self.table.get_mut(&pipeline_future).unwrap();
+        Ok(pipeline_future.take_result().map(|result| match result {
+            Ok(pipeline) => Ok(self.table.push(pipeline).unwrap()),
+            Err(error) => Err(self.table.push(error).unwrap()),
+        }))
+    }
+
+    fn drop(
+        &mut self,
+        pipeline_future: Resource<webgpu::GpuComputePipelineFuture>,
+    ) -> wasmtime::Result<()> {
+        self.table.delete(pipeline_future).unwrap();
+        Ok(())
+    }
+}
+impl webgpu::HostGpuRenderPipelineFuture for HostState {
+    fn subscribe(
+        &mut self,
+        pipeline_future: Resource<webgpu::GpuRenderPipelineFuture>,
+    ) -> wasmtime::Result<Resource<webgpu::Pollable>> {
+        Ok(preview2::subscribe(self.table_mut(), pipeline_future).unwrap())
+    }
+
+    fn get(
+        &mut self,
+        pipeline_future: Resource<webgpu::GpuRenderPipelineFuture>,
+    ) -> wasmtime::Result<
+        Option<Result<Resource<webgpu::GpuRenderPipeline>, Resource<webgpu::GpuPipelineError>>>,
+    > {
+        let pipeline_future = self.table.get_mut(&pipeline_future).unwrap();
+        Ok(pipeline_future.take_result().map(|result| match result {
+            Ok(pipeline) => Ok(self.table.push(pipeline).unwrap()),
+            Err(error) => Err(self.table.push(error).unwrap()),
+        }))
+    }
+
+    fn drop(
+        &mut self,
+        pipeline_future: Resource<webgpu::GpuRenderPipelineFuture>,
+    ) -> wasmtime::Result<()> {
+        self.table.delete(pipeline_future).unwrap();
+        Ok(())
     }
 }
 impl webgpu::HostGpuCompilationMessage for HostState {
     fn message(
         &mut self,
-        _self_: Resource<webgpu::GpuCompilationMessage>,
+        message: Resource<webgpu::GpuCompilationMessage>,
     ) -> wasmtime::Result<String> {
-        todo!()
+        Ok(self.table.get(&message).unwrap().message.clone())
     }
 
     fn type_(
         &mut self,
-        _self_: Resource<webgpu::GpuCompilationMessage>,
+        message: Resource<webgpu::GpuCompilationMessage>,
     ) -> wasmtime::Result<webgpu::GpuCompilationMessageType> {
-        todo!()
+        Ok(self.table.get(&message).unwrap().type_)
     }
 
     fn line_num(
         &mut self,
-        _self_: Resource<webgpu::GpuCompilationMessage>,
+        message: Resource<webgpu::GpuCompilationMessage>,
     ) -> wasmtime::Result<u64> {
-        todo!()
+        Ok(self.table.get(&message).unwrap().line_num)
     }
 
     fn line_pos(
         &mut self,
-        _self_: Resource<webgpu::GpuCompilationMessage>,
+        message: Resource<webgpu::GpuCompilationMessage>,
     ) -> wasmtime::Result<u64> {
-        todo!()
+        Ok(self.table.get(&message).unwrap().line_pos)
     }
 
-    fn offset(&mut self, _self_: Resource<webgpu::GpuCompilationMessage>) -> wasmtime::Result<u64> {
-        todo!()
+    fn offset(
+        &mut self,
+        message: Resource<webgpu::GpuCompilationMessage>,
+    ) -> wasmtime::Result<u64> {
+        Ok(self.table.get(&message).unwrap().offset)
     }
 
-    fn length(&mut self, _self_: Resource<webgpu::GpuCompilationMessage>) -> wasmtime::Result<u64> {
-        todo!()
+    fn length(
+        &mut self,
+        message: Resource<webgpu::GpuCompilationMessage>,
+    ) -> wasmtime::Result<u64> {
+        Ok(self.table.get(&message).unwrap().length)
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuCompilationMessage>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(&mut self, message: Resource<webgpu::GpuCompilationMessage>) -> wasmtime::Result<()> {
+        self.table.delete(message).unwrap();
+        Ok(())
     }
 }
 impl webgpu::HostGpuCompilationInfo for HostState {
-    fn drop(&mut self, _rep: Resource<webgpu::GpuCompilationInfo>) -> wasmtime::Result<()> {
-        todo!()
+    fn messages(
+        &mut self,
+        info: Resource<webgpu::GpuCompilationInfo>,
+    ) -> wasmtime::Result<Vec<Resource<webgpu::GpuCompilationMessage>>> {
+        let messages = self.table.get(&info).unwrap().messages.clone();
+        Ok(messages
+            .into_iter()
+            .map(|message| self.table.push(message).unwrap())
+            .collect())
+    }
+
+    fn drop(&mut self, info: Resource<webgpu::GpuCompilationInfo>) -> wasmtime::Result<()> {
+        self.table.delete(info).unwrap();
+        Ok(())
     }
 }
 impl webgpu::HostGpuQuerySet for HostState {
-    fn destroy(&mut self, _self_: Resource<webgpu::GpuQuerySet>) -> wasmtime::Result<()> {
-        todo!()
+    fn destroy(&mut self, query_set: Resource<webgpu::GpuQuerySet>) -> wasmtime::Result<()> {
+        // wgpu-core 0.18 has no query set destroy, but dropping it frees the queries once no command buffer uses them anymore.
+        let query_set = self.table.get_mut(&query_set).unwrap();
+        if query_set.valid && !query_set.destroyed {
+            self.instance
+                .query_set_drop::<crate::Backend>(query_set.query_set);
+        }
+        query_set.destroyed = true;
+        Ok(())
     }
 
     fn type_(
         &mut self,
-        _self_: Resource<webgpu::GpuQuerySet>,
+        query_set: Resource<webgpu::GpuQuerySet>,
     ) -> wasmtime::Result<webgpu::GpuQueryType> {
-        todo!()
+        let query_set = self.table.get(&query_set).unwrap();
+        Ok(query_set.type_)
     }
 
     fn count(
         &mut self,
-        _self_: Resource<webgpu::GpuQuerySet>,
+        query_set: Resource<webgpu::GpuQuerySet>,
     ) -> wasmtime::Result<webgpu::GpuSize32Out> {
-        todo!()
+        let query_set = self.table.get(&query_set).unwrap();
+        Ok(query_set.count)
     }
 
-    fn label(&mut self, _self_: Resource<webgpu::GpuQuerySet>) -> wasmtime::Result<String> {
-        todo!()
+    fn label(&mut self, query_set: Resource<webgpu::GpuQuerySet>) -> wasmtime::Result<String> {
+        Ok(self.table.get(&query_set).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<webgpu::GpuQuerySet>,
-        _label: String,
+        query_set: Resource<webgpu::GpuQuerySet>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&query_set).unwrap().label = label;
+        Ok(())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuQuerySet>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(&mut self, query_set: Resource<webgpu::GpuQuerySet>) -> wasmtime::Result<()> {
+        let query_set = self.table.delete(query_set).unwrap();
+        if query_set.valid && !query_set.destroyed {
+            self.instance
+                .query_set_drop::<crate::Backend>(query_set.query_set);
+        }
+        Ok(())
     }
 }
 impl webgpu::HostGpuRenderBundleEncoder for HostState {
     fn finish(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundleEncoder>,
-        _descriptor: Option<webgpu::GpuRenderBundleDescriptor>,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
+        descriptor: Option<webgpu::GpuRenderBundleDescriptor>,
     ) -> wasmtime::Result<Resource<webgpu::GpuRenderBundle>> {
-        todo!()
+        let render_bundle_encoder = self.table.delete(render_bundle_encoder).unwrap();
+        let label = descriptor.and_then(|d| d.label);
+        if render_bundle_encoder.debug_group_depth != 0 {
+            self.handle_error(
+                &render_bundle_encoder.error_sink,
+                unbalanced_debug_groups(render_bundle_encoder.debug_group_depth),
+            );
+        }
+        // can't use to_core because wgpu_types::RenderBundleDescriptor has no Default.
+        let descriptor = wgpu_types::RenderBundleDescriptor {
+            label: label.as_ref().map(|l| l.into()),
+        };
+        let render_bundle = self.handle_core_result(
+            &render_bundle_encoder.error_sink,
+            self.instance
+                .render_bundle_encoder_finish::<crate::Backend>(
+                    render_bundle_encoder.render_bundle_encoder,
+                    &descriptor,
+                    (),
+                ),
+        );
+        Ok(self.table.push(Labeled::new(render_bundle, label)).unwrap())
     }
 
     fn label(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundleEncoder>,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
     ) -> wasmtime::Result<String> {
-        todo!()
+        Ok(self
+            .table
+            .get(&render_bundle_encoder)
+            .unwrap()
+            .label
+            .clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundleEncoder>,
-        _label: String,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&render_bundle_encoder).unwrap().label = label;
+        Ok(())
     }
 
     fn push_debug_group(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundleEncoder>,
-        _group_label: String,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
+        group_label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let render_bundle_encoder = self.table.get_mut(&render_bundle_encoder).unwrap();
+        render_bundle_encoder.debug_group_depth += 1;
+        let group_label = debug_label(group_label);
+        // TODO: validate safety.
+        unsafe {
+            wgpu_core::command::bundle_ffi::wgpu_render_bundle_push_debug_group(
+                &mut render_bundle_encoder.render_bundle_encoder,
+                group_label.as_ptr(),
+            )
+        };
+        Ok(())
     }
 
     fn pop_debug_group(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundleEncoder>,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let host_encoder = self.table.get_mut(&render_bundle_encoder).unwrap();
+        // wgpu-core 0.18 ignores debug groups in bundles, so nothing else checks this.
+        if host_encoder.debug_group_depth == 0 {
+            let render_bundle_encoder = self.table.get(&render_bundle_encoder).unwrap();
+            self.handle_error(&render_bundle_encoder.error_sink, pop_empty_debug_group());
+            return Ok(());
+        }
+        host_encoder.debug_group_depth -= 1;
+        wgpu_core::command::bundle_ffi::wgpu_render_bundle_pop_debug_group(
+            &mut host_encoder.render_bundle_encoder,
+        );
+        Ok(())
     }
 
     fn insert_debug_marker(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundleEncoder>,
-        _marker_label: String,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
+        marker_label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let render_bundle_encoder = &mut self
+            .table
+            .get_mut(&render_bundle_encoder)
+            .unwrap()
+            .render_bundle_encoder;
+        let marker_label = debug_label(marker_label);
+        // TODO: validate safety.
+        unsafe {
+            wgpu_core::command::bundle_ffi::wgpu_render_bundle_insert_debug_marker(
+                render_bundle_encoder,
+                marker_label.as_ptr(),
+            )
+        };
+        Ok(())
     }
 
     fn set_bind_group(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundleEncoder>,
-        _index: webgpu::GpuIndex32,
-        _bind_group: Resource<webgpu::GpuBindGroup>,
-        _dynamic_offsets: Option<Vec<webgpu::GpuBufferDynamicOffset>>,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
+        index: webgpu::GpuIndex32,
+        bind_group: Resource<webgpu::GpuBindGroup>,
+        dynamic_offsets: Option<Vec<webgpu::GpuBufferDynamicOffset>>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let bind_group = self.table.get(&bind_group).unwrap().inner;
+        let render_bundle_encoder = &mut self
+            .table
+            .get_mut(&render_bundle_encoder)
+            .unwrap()
+            .render_bundle_encoder;
+        let dynamic_offsets = dynamic_offsets.unwrap_or_default();
+        // TODO: validate safety.
+        unsafe {
+            wgpu_core::command::bundle_ffi::wgpu_render_bundle_set_bind_group(
+                render_bundle_encoder,
+                index,
+                bind_group,
+                dynamic_offsets.as_ptr(),
+                dynamic_offsets.len(),
+            )
+        };
+        Ok(())
     }
 
     fn set_pipeline(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundleEncoder>,
-        _pipeline: Resource<wgpu_core::id::RenderPipelineId>,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
+        pipeline: Resource<webgpu::GpuRenderPipeline>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let pipeline = pipeline.to_core(&self.table);
+        let render_bundle_encoder = &mut self
+            .table
+            .get_mut(&render_bundle_encoder)
+            .unwrap()
+            .render_bundle_encoder;
+        wgpu_core::command::bundle_ffi::wgpu_render_bundle_set_pipeline(
+            render_bundle_encoder,
+            pipeline,
+        );
+        Ok(())
     }
 
     fn set_index_buffer(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundleEncoder>,
-        _buffer: Resource<webgpu::GpuBuffer>,
-        _index_format: webgpu::GpuIndexFormat,
-        _offset: Option<webgpu::GpuSize64>,
-        _size: Option<webgpu::GpuSize64>,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
+        buffer: Resource<webgpu::GpuBuffer>,
+        index_format: webgpu::GpuIndexFormat,
+        offset: Option<webgpu::GpuSize64>,
+        size: Option<webgpu::GpuSize64>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let buffer = self.table.get(&buffer).unwrap().buffer;
+        let size = match size.map(buffer_binding_size).transpose() {
+            Ok(size) => size,
+            Err(e) => {
+                let render_bundle_encoder = self.table.get(&render_bundle_encoder).unwrap();
+                self.handle_error(&render_bundle_encoder.error_sink, e);
+                return Ok(());
+            }
+        };
+        let render_bundle_encoder = &mut self
+            .table
+            .get_mut(&render_bundle_encoder)
+            .unwrap()
+            .render_bundle_encoder;
+        wgpu_core::command::bundle_ffi::wgpu_render_bundle_set_index_buffer(
+            render_bundle_encoder,
+            buffer,
+            index_format.into(),
+            offset.unwrap_or(0),
+            size,
+        );
+        Ok(())
     }
 
     fn set_vertex_buffer(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundleEncoder>,
-        _slot: webgpu::GpuIndex32,
-        _buffer: Resource<webgpu::GpuBuffer>,
-        _offset: Option<webgpu::GpuSize64>,
-        _size: Option<webgpu::GpuSize64>,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
+        slot: webgpu::GpuIndex32,
+        buffer: Resource<webgpu::GpuBuffer>,
+        offset: Option<webgpu::GpuSize64>,
+        size: Option<webgpu::GpuSize64>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let buffer = self.table.get(&buffer).unwrap().buffer;
+        let size = match size.map(buffer_binding_size).transpose() {
+            Ok(size) => size,
+            Err(e) => {
+                let render_bundle_encoder = self.table.get(&render_bundle_encoder).unwrap();
+                self.handle_error(&render_bundle_encoder.error_sink, e);
+                return Ok(());
+            }
+        };
+        let render_bundle_encoder = &mut self
+            .table
+            .get_mut(&render_bundle_encoder)
+            .unwrap()
+            .render_bundle_encoder;
+        wgpu_core::command::bundle_ffi::wgpu_render_bundle_set_vertex_buffer(
+            render_bundle_encoder,
+            slot,
+            buffer,
+            offset.unwrap_or(0),
+            size,
+        );
+        Ok(())
     }
 
     fn draw(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundleEncoder>,
-        _vertex_count: webgpu::GpuSize32,
-        _instance_count: Option<webgpu::GpuSize32>,
-        _first_vertex: Option<webgpu::GpuSize32>,
-        _first_instance: Option<webgpu::GpuSize32>,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
+        vertex_count: webgpu::GpuSize32,
+        instance_count: Option<webgpu::GpuSize32>,
+        first_vertex: Option<webgpu::GpuSize32>,
+        first_instance: Option<webgpu::GpuSize32>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let render_bundle_encoder = &mut self
+            .table
+            .get_mut(&render_bundle_encoder)
+            .unwrap()
+            .render_bundle_encoder;
+        // https://www.w3.org/TR/webgpu/#dom-gpurendercommandsmixin-draw
+        wgpu_core::command::bundle_ffi::wgpu_render_bundle_draw(
+            render_bundle_encoder,
+            vertex_count,
+            instance_count.unwrap_or(1),
+            first_vertex.unwrap_or(0),
+            first_instance.unwrap_or(0),
+        );
+        Ok(())
     }
 
     fn draw_indexed(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundleEncoder>,
-        _index_count: webgpu::GpuSize32,
-        _instance_count: Option<webgpu::GpuSize32>,
-        _first_index: Option<webgpu::GpuSize32>,
-        _base_vertex: Option<webgpu::GpuSignedOffset32>,
-        _first_instance: Option<webgpu::GpuSize32>,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
+        index_count: webgpu::GpuSize32,
+        instance_count: Option<webgpu::GpuSize32>,
+        first_index: Option<webgpu::GpuSize32>,
+        base_vertex: Option<webgpu::GpuSignedOffset32>,
+        first_instance: Option<webgpu::GpuSize32>,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let render_bundle_encoder = &mut self
+            .table
+            .get_mut(&render_bundle_encoder)
+            .unwrap()
+            .render_bundle_encoder;
+        // https://www.w3.org/TR/webgpu/#dom-gpurendercommandsmixin-drawindexed
+        wgpu_core::command::bundle_ffi::wgpu_render_bundle_draw_indexed(
+            render_bundle_encoder,
+            index_count,
+            instance_count.unwrap_or(1),
+            first_index.unwrap_or(0),
+            base_vertex.unwrap_or(0),
+            first_instance.unwrap_or(0),
+        );
+        Ok(())
     }
 
     fn draw_indirect(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundleEncoder>,
-        _indirect_buffer: Resource<webgpu::GpuBuffer>,
-        _indirect_offset: webgpu::GpuSize64,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
+        indirect_buffer: Resource<webgpu::GpuBuffer>,
+        indirect_offset: webgpu::GpuSize64,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let indirect_buffer = self.table.get(&indirect_buffer).unwrap().buffer;
+        let render_bundle_encoder = &mut self
+            .table
+            .get_mut(&render_bundle_encoder)
+            .unwrap()
+            .render_bundle_encoder;
+        wgpu_core::command::bundle_ffi::wgpu_render_bundle_draw_indirect(
+            render_bundle_encoder,
+            indirect_buffer,
+            indirect_offset,
+        );
+        Ok(())
     }
 
     fn draw_indexed_indirect(
         &mut self,
-        _self_: Resource<webgpu::GpuRenderBundleEncoder>,
-        _indirect_buffer: Resource<webgpu::GpuBuffer>,
-        _indirect_offset: webgpu::GpuSize64,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
+        indirect_buffer: Resource<webgpu::GpuBuffer>,
+        indirect_offset: webgpu::GpuSize64,
     ) -> wasmtime::Result<()> {
-        todo!()
+        let indirect_buffer = self.table.get(&indirect_buffer).unwrap().buffer;
+        let render_bundle_encoder = &mut self
+            .table
+            .get_mut(&render_bundle_encoder)
+            .unwrap()
+            .render_bundle_encoder;
+        wgpu_core::command::bundle_ffi::wgpu_render_bundle_draw_indexed_indirect(
+            render_bundle_encoder,
+            indirect_buffer,
+            indirect_offset,
+        );
+        Ok(())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuRenderBundleEncoder>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(
+        &mut self,
+        render_bundle_encoder: Resource<webgpu::GpuRenderBundleEncoder>,
+    ) -> wasmtime::Result<()> {
+        self.table.delete(render_bundle_encoder).unwrap();
+        Ok(())
     }
 }
 impl webgpu::HostGpuComputePipeline for HostState {
-    fn label(&mut self, _self_: Resource<webgpu::GpuComputePipeline>) -> wasmtime::Result<String> {
-        todo!()
+    fn label(
+        &mut self,
+        compute_pipeline: Resource<webgpu::GpuComputePipeline>,
+    ) -> wasmtime::Result<String> {
+        Ok(self.table.get(&compute_pipeline).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<webgpu::GpuComputePipeline>,
-        _label: String,
+        compute_pipeline: Resource<webgpu::GpuComputePipeline>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&compute_pipeline).unwrap().label = label;
+        Ok(())
     }
 
     fn get_bind_group_layout(
         &mut self,
-        _self_: Resource<webgpu::GpuComputePipeline>,
-        _index: u32,
+        compute_pipeline: Resource<webgpu::GpuComputePipeline>,
+        index: u32,
     ) -> wasmtime::Result<Resource<webgpu::GpuBindGroupLayout>> {
-        todo!()
+        let compute_pipeline = self.table.get(&compute_pipeline).unwrap();
+        // wgpu-core returns the id of the layout the pipeline already uses and bumps its ref count, so dropping it like any other bind group layout just releases that reference.
+        let bind_group_layout = self.handle_core_result(
+            &compute_pipeline.error_sink,
+            self.instance
+                .compute_pipeline_get_bind_group_layout::<crate::Backend>(
+                    compute_pipeline.inner,
+                    index,
+                    (),
+                ),
+        );
+        Ok(self
+            .table
+            .push(Labeled::new(bind_group_layout, None))
+            .unwrap())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuComputePipeline>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(
+        &mut self,
+        compute_pipeline: Resource<webgpu::GpuComputePipeline>,
+    ) -> wasmtime::Result<()> {
+        let compute_pipeline = self.table.delete(compute_pipeline).unwrap();
+        self.instance
+            .compute_pipeline_drop::<crate::Backend>(compute_pipeline.inner);
+        Ok(())
     }
 }
 impl webgpu::HostGpuBindGroup for HostState {
-    fn label(&mut self, _self_: Resource<webgpu::GpuBindGroup>) -> wasmtime::Result<String> {
-        todo!()
+    fn label(&mut self, bind_group: Resource<webgpu::GpuBindGroup>) -> wasmtime::Result<String> {
+        Ok(self.table.get(&bind_group).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<webgpu::GpuBindGroup>,
-        _label: String,
+        bind_group: Resource<webgpu::GpuBindGroup>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&bind_group).unwrap().label = label;
+        Ok(())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuBindGroup>) -> wasmtime::Result<()> {
+    fn drop(&mut self, bind_group: Resource<webgpu::GpuBindGroup>) -> wasmtime::Result<()> {
+        let bind_group = self.table.delete(bind_group).unwrap();
+        self.instance
+            .bind_group_drop::<crate::Backend>(bind_group.inner);
         Ok(())
     }
 }
 impl webgpu::HostGpuPipelineLayout for HostState {
-    fn label(&mut self, _self_: Resource<webgpu::GpuPipelineLayout>) -> wasmtime::Result<String> {
-        todo!()
+    fn label(
+        &mut self,
+        pipeline_layout: Resource<webgpu::GpuPipelineLayout>,
+    ) -> wasmtime::Result<String> {
+        Ok(self.table.get(&pipeline_layout).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<webgpu::GpuPipelineLayout>,
-        _label: String,
+        pipeline_layout: Resource<webgpu::GpuPipelineLayout>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&pipeline_layout).unwrap().label = label;
+        Ok(())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuPipelineLayout>) -> wasmtime::Result<()> {
+    fn drop(
+        &mut self,
+        pipeline_layout: Resource<webgpu::GpuPipelineLayout>,
+    ) -> wasmtime::Result<()> {
+        let pipeline_layout = self.table.delete(pipeline_layout).unwrap();
+        self.instance
+            .pipeline_layout_drop::<crate::Backend>(pipeline_layout.inner);
         Ok(())
     }
 }
 impl webgpu::HostGpuBindGroupLayout for HostState {
-    fn label(&mut self, _self_: Resource<webgpu::GpuBindGroupLayout>) -> wasmtime::Result<String> {
-        todo!()
+    fn label(
+        &mut self,
+        bind_group_layout: Resource<webgpu::GpuBindGroupLayout>,
+    ) -> wasmtime::Result<String> {
+        Ok(self.table.get(&bind_group_layout).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<webgpu::GpuBindGroupLayout>,
-        _label: String,
+        bind_group_layout: Resource<webgpu::GpuBindGroupLayout>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&bind_group_layout).unwrap().label = label;
+        Ok(())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuBindGroupLayout>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(
+        &mut self,
+        bind_group_layout: Resource<webgpu::GpuBindGroupLayout>,
+    ) -> wasmtime::Result<()> {
+        let bind_group_layout = self.table.delete(bind_group_layout).unwrap();
+        self.instance
+            .bind_group_layout_drop::<crate::Backend>(bind_group_layout.inner);
+        Ok(())
     }
 }
 impl webgpu::HostGpuExternalTexture for HostState {
@@ -1765,52 +4298,116 @@ impl webgpu::HostGpuExternalTexture for HostState {
     }
 }
 impl webgpu::HostGpuSampler for HostState {
-    fn label(&mut self, _self_: Resource<webgpu::GpuSampler>) -> wasmtime::Result<String> {
-        todo!()
+    fn label(&mut self, sampler: Resource<webgpu::GpuSampler>) -> wasmtime::Result<String> {
+        Ok(self.table.get(&sampler).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<webgpu::GpuSampler>,
-        _label: String,
+        sampler: Resource<webgpu::GpuSampler>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&sampler).unwrap().label = label;
+        Ok(())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuSampler>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(&mut self, sampler: Resource<webgpu::GpuSampler>) -> wasmtime::Result<()> {
+        let sampler = self.table.delete(sampler).unwrap();
+        self.instance.sampler_drop::<crate::Backend>(sampler.inner);
+        Ok(())
     }
 }
 impl webgpu::HostGpuBuffer for HostState {
     fn size(
         &mut self,
-        _self_: Resource<webgpu::GpuBuffer>,
+        buffer: Resource<webgpu::GpuBuffer>,
     ) -> wasmtime::Result<webgpu::GpuSize64Out> {
-        todo!()
+        let buffer = self.table.get(&buffer).unwrap();
+        Ok(buffer.descriptor.size)
     }
 
     fn usage(
         &mut self,
-        _self_: Resource<webgpu::GpuBuffer>,
+        buffer: Resource<webgpu::GpuBuffer>,
     ) -> wasmtime::Result<webgpu::GpuFlagsConstant> {
-        todo!()
+        let buffer = self.table.get(&buffer).unwrap();
+        Ok(buffer.descriptor.usage.bits())
     }
 
     fn map_state(
         &mut self,
-        _self_: Resource<webgpu::GpuBuffer>,
+        buffer: Resource<webgpu::GpuBuffer>,
     ) -> wasmtime::Result<webgpu::GpuBufferMapState> {
-        todo!()
+        let buffer = self.table.get(&buffer).unwrap();
+        let map_state = *buffer.map_state.lock().unwrap();
+        Ok(map_state)
     }
 
     fn map_async(
         &mut self,
-        _self_: Resource<webgpu::GpuBuffer>,
-        _mode: webgpu::GpuMapModeFlags,
-        _offset: Option<webgpu::GpuSize64>,
-        _size: Option<webgpu::GpuSize64>,
-    ) -> wasmtime::Result<()> {
-        todo!()
+        buffer: Resource<webgpu::GpuBuffer>,
+        mode: webgpu::GpuMapModeFlags,
+        offset: Option<webgpu::GpuSize64>,
+        size: Option<webgpu::GpuSize64>,
+    ) -> wasmtime::Result<Resource<webgpu::GpuBufferMapFuture>> {
+        let buffer = self.table.get(&buffer).unwrap();
+        let map_future = BufferMapFuture {
+            instance: Arc::clone(&self.instance),
+            device: buffer.error_sink.device,
+            error_sink: buffer.error_sink.clone(),
+            sender: self.sender.clone(),
+            result: Default::default(),
+            notify: Default::default(),
+            poll: None,
+        };
+
+        // a buffer that's already mapped, or about to be, keeps its state.
+        let args = match *buffer.map_state.lock().unwrap() {
+            webgpu::GpuBufferMapState::Unmapped => {
+                map_async_args(buffer.descriptor.size, mode, offset, size)
+            }
+            webgpu::GpuBufferMapState::Pending => Err(ValidationError(
+                "map-async called on a buffer that is already being mapped".to_string(),
+            )),
+            webgpu::GpuBufferMapState::Mapped => Err(ValidationError(
+                "map-async called on a buffer that is already mapped".to_string(),
+            )),
+        };
+        let (host, range) = match args {
+            Ok(args) => args,
+            Err(e) => {
+                // the map future is rejected right away.
+                *map_future.result.lock().unwrap() = Some(Err(e.to_string()));
+                self.handle_error(&buffer.error_sink, e);
+                return Ok(self.table.push(map_future).unwrap());
+            }
+        };
+
+        *buffer.map_state.lock().unwrap() = webgpu::GpuBufferMapState::Pending;
+        let callback_result = Arc::clone(&map_future.result);
+        let callback_notify = Arc::clone(&map_future.notify);
+        let callback_map_state = Arc::clone(&buffer.map_state);
+        let callback = wgpu_core::resource::BufferMapCallback::from_rust(Box::new(
+            move |map_result: wgpu_core::resource::BufferAccessResult| {
+                *callback_map_state.lock().unwrap() = match map_result {
+                    Ok(()) => webgpu::GpuBufferMapState::Mapped,
+                    Err(_) => webgpu::GpuBufferMapState::Unmapped,
+                };
+                *callback_result.lock().unwrap() = Some(map_result.map_err(|e| e.to_string()));
+                callback_notify.notify_one();
+            },
+        ));
+
+        // wgpu-core also calls the callback on error, so the map future still resolves.
+        if let Err(e) = self.instance.buffer_map_async::<crate::Backend>(
+            buffer.buffer,
+            range,
+            wgpu_core::resource::BufferMapOperation { host, callback },
+        ) {
+            self.handle_error(&buffer.error_sink, e);
+        }
+
+        Ok(self.table.push(map_future).unwrap())
     }
 
     fn get_mapped_range(
@@ -1818,47 +4415,94 @@ impl webgpu::HostGpuBuffer for HostState {
         buffer: Resource<webgpu::GpuBuffer>,
         offset: Option<webgpu::GpuSize64>,
         size: Option<webgpu::GpuSize64>,
-    ) -> wasmtime::Result<Resource<webgpu::GpuBuffer>> {
-        let buffer_rep = buffer.rep();
-        let buffer = self.table.get_mut(&buffer).unwrap();
-        let (ptr, len) = self
-            .instance
-            .buffer_get_mapped_range::<crate::Backend>(buffer.buffer, offset.unwrap_or(0), size)
-            .unwrap();
-        let remote_buffer = RemoteBuffer {
-            ptr,
-            len,
+    ) -> wasmtime::Result<Resource<webgpu::RemoteBuffer>> {
+        let host_buffer = self.table.get(&buffer).unwrap();
+        let mapped_range = match self.instance.buffer_get_mapped_range::<crate::Backend>(
+            host_buffer.buffer,
+            offset.unwrap_or(0),
+            size,
+        ) {
+            Ok((ptr, len)) => MappedRange::new(RemoteBuffer { ptr, len }),
+            Err(e) => {
+                self.handle_error(&host_buffer.error_sink, e);
+                // the guest gets an empty range to read from.
+                MappedRange::default()
+            }
         };
-        buffer.mapped = Some(remote_buffer);
-        Ok(Resource::new_own(buffer_rep))
+        self.table
+            .get_mut(&buffer)
+            .unwrap()
+            .mapped_ranges
+            .push(mapped_range.clone());
+        Ok(self.table.push(mapped_range).unwrap())
     }
 
     fn unmap(&mut self, buffer: Resource<webgpu::GpuBuffer>) -> wasmtime::Result<()> {
-        let buffer = self.table.get_mut(&buffer).unwrap();
-        buffer.mapped.take().unwrap();
-        self.instance
-            .buffer_unmap::<crate::Backend>(buffer.buffer)
-            .unwrap();
+        self.table.get_mut(&buffer).unwrap().detach_mapped_ranges();
+        let buffer = self.table.get(&buffer).unwrap();
+        if let Err(e) = self.instance.buffer_unmap::<crate::Backend>(buffer.buffer) {
+            self.handle_error(&buffer.error_sink, e);
+        }
         Ok(())
     }
 
-    fn destroy(&mut self, _self_: Resource<webgpu::GpuBuffer>) -> wasmtime::Result<()> {
-        todo!()
+    fn destroy(&mut self, buffer: Resource<webgpu::GpuBuffer>) -> wasmtime::Result<()> {
+        // https://www.w3.org/TR/webgpu/#dom-gpubuffer-destroy
+        // destroying a buffer unmaps it.
+        let host_buffer = self.table.get_mut(&buffer).unwrap();
+        host_buffer.detach_mapped_ranges();
+        host_buffer.destroyed = true;
+        let buffer = self.table.get(&buffer).unwrap();
+        if let Err(e) = self
+            .instance
+            .buffer_destroy::<crate::Backend>(buffer.buffer)
+        {
+            self.handle_error(&buffer.error_sink, e);
+        }
+        Ok(())
     }
 
-    fn label(&mut self, _self_: Resource<webgpu::GpuBuffer>) -> wasmtime::Result<String> {
-        todo!()
+    fn label(&mut self, buffer: Resource<webgpu::GpuBuffer>) -> wasmtime::Result<String> {
+        Ok(self.table.get(&buffer).unwrap().label.clone())
     }
 
     fn set_label(
         &mut self,
-        _self_: Resource<webgpu::GpuBuffer>,
-        _label: String,
+        buffer: Resource<webgpu::GpuBuffer>,
+        label: String,
     ) -> wasmtime::Result<()> {
-        todo!()
+        self.table.get_mut(&buffer).unwrap().label = label;
+        Ok(())
+    }
+
+    fn drop(&mut self, buffer: Resource<webgpu::GpuBuffer>) -> wasmtime::Result<()> {
+        let mut buffer = self.table.delete(buffer).unwrap();
+        // wgpu-core frees the mapping along with the buffer.
+        buffer.detach_mapped_ranges();
+        self.instance
+            .buffer_drop::<crate::Backend>(buffer.buffer, false);
+        Ok(())
+    }
+}
+impl webgpu::HostGpuBufferMapFuture for HostState {
+    fn subscribe(
+        &mut self,
+        map_future: Resource<webgpu::GpuBufferMapFuture>,
+    ) -> wasmtime::Result<Resource<webgpu::Pollable>> {
+        Ok(preview2::subscribe(self.table_mut(), map_future).unwrap())
+    }
+
+    fn get(
+        &mut self,
+        map_future: Resource<webgpu::GpuBufferMapFuture>,
+    ) -> wasmtime::Result<Option<Result<(), String>>> {
+        let map_future = self.table.get(&map_future).unwrap();
+        let result = map_future.result.lock().unwrap().clone();
+        Ok(result)
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuBuffer>) -> wasmtime::Result<()> {
+    fn drop(&mut self, map_future: Resource<webgpu::GpuBufferMapFuture>) -> wasmtime::Result<()> {
+        self.table.delete(map_future).unwrap();
         Ok(())
     }
 }
@@ -1866,30 +4510,35 @@ impl webgpu::HostGpu for HostState {
     fn request_adapter(
         &mut self,
         _self_: Resource<webgpu::Gpu>,
-        _options: Option<webgpu::GpuRequestAdapterOptions>,
-    ) -> wasmtime::Result<Resource<wgpu_core::id::AdapterId>> {
-        let adapter = self
-            .instance
-            .request_adapter(
-                &Default::default(),
-                wgpu_core::instance::AdapterInputs::Mask(wgpu_types::Backends::all(), |_| ()),
-            )
-            .unwrap();
-        Ok(self.table.push(adapter).unwrap())
+        options: Option<webgpu::GpuRequestAdapterOptions>,
+    ) -> wasmtime::Result<Option<Resource<wgpu_core::id::AdapterId>>> {
+        let mut options = options.map(|o| o.to_core(&self.table)).unwrap_or_default();
+        options.force_fallback_adapter |= self.force_fallback_adapter;
+        let adapter = self.instance.request_adapter(
+            &options,
+            wgpu_core::instance::AdapterInputs::Mask(wgpu_types::Backends::all(), |_| ()),
+        );
+        match adapter {
+            Ok(adapter) => Ok(Some(self.table.push(adapter).unwrap())),
+            Err(e) => {
+                log::warn!("no adapter found: {e}");
+                Ok(None)
+            }
+        }
     }
 
     fn get_preferred_canvas_format(
         &mut self,
         _self_: Resource<webgpu::Gpu>,
     ) -> wasmtime::Result<webgpu::GpuTextureFormat> {
-        todo!()
+        Ok(self.preferred_canvas_format.into())
     }
 
     fn wgsl_language_features(
         &mut self,
         _self_: Resource<webgpu::Gpu>,
     ) -> wasmtime::Result<Resource<webgpu::WgslLanguageFeatures>> {
-        todo!()
+        Ok(self.table.push(WgslLanguageFeatures).unwrap())
     }
 
     fn drop(&mut self, _rep: Resource<webgpu::Gpu>) -> wasmtime::Result<()> {
@@ -1897,43 +4546,48 @@ impl webgpu::HostGpu for HostState {
     }
 }
 impl webgpu::HostGpuAdapterInfo for HostState {
-    fn vendor(&mut self, _self_: Resource<webgpu::GpuAdapterInfo>) -> wasmtime::Result<String> {
-        todo!()
+    // https://www.w3.org/TR/webgpu/#gpuadapterinfo
+    // wgpu only knows pci ids, not names, so vendor and device are ids in hex.
+    fn vendor(&mut self, info: Resource<webgpu::GpuAdapterInfo>) -> wasmtime::Result<String> {
+        let info = self.table.get(&info).unwrap();
+        Ok(format!("{:#06x}", info.vendor))
     }
 
     fn architecture(
         &mut self,
-        _self_: Resource<webgpu::GpuAdapterInfo>,
+        _info: Resource<webgpu::GpuAdapterInfo>,
     ) -> wasmtime::Result<String> {
-        todo!()
+        // not exposed by wgpu.
+        Ok(String::new())
     }
 
-    fn device(&mut self, _self_: Resource<webgpu::GpuAdapterInfo>) -> wasmtime::Result<String> {
-        todo!()
+    fn device(&mut self, info: Resource<webgpu::GpuAdapterInfo>) -> wasmtime::Result<String> {
+        let info = self.table.get(&info).unwrap();
+        Ok(format!("{:#06x}", info.device))
     }
 
-    fn description(
-        &mut self,
-        _self_: Resource<webgpu::GpuAdapterInfo>,
-    ) -> wasmtime::Result<String> {
-        todo!()
+    fn description(&mut self, info: Resource<webgpu::GpuAdapterInfo>) -> wasmtime::Result<String> {
+        let info = self.table.get(&info).unwrap();
+        Ok(info.name.clone())
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuAdapterInfo>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(&mut self, info: Resource<webgpu::GpuAdapterInfo>) -> wasmtime::Result<()> {
+        self.table.delete(info).unwrap();
+        Ok(())
     }
 }
 impl webgpu::HostWgslLanguageFeatures for HostState {
     fn has(
         &mut self,
         _self_: Resource<webgpu::WgslLanguageFeatures>,
-        _key: String,
+        key: String,
     ) -> wasmtime::Result<bool> {
-        todo!()
+        Ok(WGSL_LANGUAGE_FEATURES.contains(&key.as_str()))
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::WgslLanguageFeatures>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(&mut self, features: Resource<webgpu::WgslLanguageFeatures>) -> wasmtime::Result<()> {
+        self.table.delete(features).unwrap();
+        Ok(())
     }
 }
 impl webgpu::HostGpuSupportedFeatures for HostState {
@@ -1968,241 +4622,273 @@ impl webgpu::HostGpuSupportedFeatures for HostState {
             }
             "bgra8unorm-storage" => features.contains(wgpu_types::Features::BGRA8UNORM_STORAGE),
             // "float32-filterable" => features.contains(wgpu_types::Features::FLOAT32_FILTERABLE),
-            _ => todo!(),
+            // https://www.w3.org/TR/webgpu/#gpusupportedfeatures
+            // features wgpu-core doesn't know about are never supported.
+            _ => false,
         })
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuSupportedFeatures>) -> wasmtime::Result<()> {
+    fn drop(&mut self, features: Resource<webgpu::GpuSupportedFeatures>) -> wasmtime::Result<()> {
+        self.table.delete(features).unwrap();
         Ok(())
     }
 }
 impl webgpu::HostGpuSupportedLimits for HostState {
     fn max_texture_dimension1_d(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_texture_dimension_1d)
     }
 
     fn max_texture_dimension2_d(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_texture_dimension_2d)
     }
 
     fn max_texture_dimension3_d(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_texture_dimension_3d)
     }
 
     fn max_texture_array_layers(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_texture_array_layers)
     }
 
     fn max_bind_groups(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_bind_groups)
     }
 
     fn max_bind_groups_plus_vertex_buffers(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        _limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        Ok(MAX_BIND_GROUPS_PLUS_VERTEX_BUFFERS)
     }
 
     fn max_bindings_per_bind_group(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_bindings_per_bind_group)
     }
 
     fn max_dynamic_uniform_buffers_per_pipeline_layout(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_dynamic_uniform_buffers_per_pipeline_layout)
     }
 
     fn max_dynamic_storage_buffers_per_pipeline_layout(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_dynamic_storage_buffers_per_pipeline_layout)
     }
 
     fn max_sampled_textures_per_shader_stage(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_sampled_textures_per_shader_stage)
     }
 
     fn max_samplers_per_shader_stage(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_samplers_per_shader_stage)
     }
 
     fn max_storage_buffers_per_shader_stage(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_storage_buffers_per_shader_stage)
     }
 
     fn max_storage_textures_per_shader_stage(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_storage_textures_per_shader_stage)
     }
 
     fn max_uniform_buffers_per_shader_stage(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_uniform_buffers_per_shader_stage)
     }
 
     fn max_uniform_buffer_binding_size(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u64> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_uniform_buffer_binding_size as u64)
     }
 
     fn max_storage_buffer_binding_size(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u64> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_storage_buffer_binding_size as u64)
     }
 
     fn min_uniform_buffer_offset_alignment(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.min_uniform_buffer_offset_alignment)
     }
 
     fn min_storage_buffer_offset_alignment(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.min_storage_buffer_offset_alignment)
     }
 
     fn max_vertex_buffers(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_vertex_buffers)
     }
 
     fn max_buffer_size(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u64> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_buffer_size)
     }
 
     fn max_vertex_attributes(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_vertex_attributes)
     }
 
     fn max_vertex_buffer_array_stride(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_vertex_buffer_array_stride)
     }
 
     fn max_inter_stage_shader_components(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_inter_stage_shader_components)
     }
 
     fn max_inter_stage_shader_variables(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        _limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        Ok(MAX_INTER_STAGE_SHADER_VARIABLES)
     }
 
     fn max_color_attachments(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        _limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        Ok(MAX_COLOR_ATTACHMENTS)
     }
 
     fn max_color_attachment_bytes_per_sample(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        _limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        Ok(MAX_COLOR_ATTACHMENT_BYTES_PER_SAMPLE)
     }
 
     fn max_compute_workgroup_storage_size(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_compute_workgroup_storage_size)
     }
 
     fn max_compute_invocations_per_workgroup(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_compute_invocations_per_workgroup)
     }
 
     fn max_compute_workgroup_size_x(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_compute_workgroup_size_x)
     }
 
     fn max_compute_workgroup_size_y(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_compute_workgroup_size_y)
     }
 
     fn max_compute_workgroup_size_z(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_compute_workgroup_size_z)
     }
 
     fn max_compute_workgroups_per_dimension(
         &mut self,
-        _self_: Resource<webgpu::GpuSupportedLimits>,
+        limits: Resource<webgpu::GpuSupportedLimits>,
     ) -> wasmtime::Result<u32> {
-        todo!()
+        let limits = self.table.get(&limits).unwrap();
+        Ok(limits.max_compute_workgroups_per_dimension)
     }
 
-    fn drop(&mut self, _rep: Resource<webgpu::GpuSupportedLimits>) -> wasmtime::Result<()> {
-        todo!()
+    fn drop(&mut self, limits: Resource<webgpu::GpuSupportedLimits>) -> wasmtime::Result<()> {
+        self.table.delete(limits).unwrap();
+        Ok(())
     }
 }
 impl webgpu::HostAllowSharedBufferSource for HostState {
@@ -2265,12 +4951,3 @@ impl webgpu::HostUint32Array for HostState {
         todo!()
     }
 }
-
-fn core_result<I, E>(
-    (id, error): (wgpu_core::id::Id<I>, Option<E>),
-) -> Result<wgpu_core::id::Id<I>, E> {
-    match error {
-        Some(error) => Err(error),
-        None => Ok(id),
-    }
-}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use wasmtime::component::ResourceTable;
 
 use crate::wasi::webgpu::webgpu;
@@ -6,24 +8,70 @@ pub trait ToCore<T> {
     fn to_core(self, table: &ResourceTable) -> T;
 }
 
-impl<T> ToCore<T> for wasmtime::component::Resource<T>
+// Like ToCore, but for values where the guest can hand us something that doesn't make sense.
+pub trait TryToCore<T> {
+    fn try_to_core(self, table: &ResourceTable) -> Result<T, ValidationError>;
+}
+
+#[derive(Debug)]
+pub struct ValidationError(pub String);
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl<T> ToCore<T> for wasmtime::component::Resource<super::Labeled<T>>
 where
     T: Copy + 'static,
 {
     fn to_core(self, table: &ResourceTable) -> T {
-        *table.get(&self).unwrap()
+        table.get(&self).unwrap().inner
     }
 }
 
-impl ToCore<wgpu_types::Extent3d> for webgpu::GpuExtent3D {
-    fn to_core(self, _table: &ResourceTable) -> wgpu_types::Extent3d {
+impl<T> ToCore<T> for wasmtime::component::Resource<super::Pipeline<T>>
+where
+    T: Copy + 'static,
+{
+    fn to_core(self, table: &ResourceTable) -> T {
+        table.get(&self).unwrap().inner
+    }
+}
+
+impl TryToCore<wgpu_types::Extent3d> for webgpu::GpuExtent3D {
+    fn try_to_core(self, _table: &ResourceTable) -> Result<wgpu_types::Extent3d, ValidationError> {
+        // https://www.w3.org/TR/webgpu/#dictdef-gpuextent3ddict
         match self {
-            webgpu::GpuExtent3D::GpuExtent3DDict(extent_dict) => wgpu_types::Extent3d {
+            webgpu::GpuExtent3D::GpuExtent3DDict(extent_dict) => Ok(wgpu_types::Extent3d {
                 width: extent_dict.width,
-                height: extent_dict.height.unwrap(),
-                depth_or_array_layers: extent_dict.depth_or_array_layers.unwrap(),
+                height: extent_dict.height.unwrap_or(1),
+                depth_or_array_layers: extent_dict.depth_or_array_layers.unwrap_or(1),
+            }),
+            webgpu::GpuExtent3D::ListGpuIntegerCoordinate(coordinates) => match coordinates[..] {
+                [width] => Ok(wgpu_types::Extent3d {
+                    width,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                }),
+                [width, height] => Ok(wgpu_types::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                }),
+                [width, height, depth_or_array_layers] => Ok(wgpu_types::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers,
+                }),
+                _ => Err(ValidationError(format!(
+                    "GPUExtent3D needs between 1 and 3 coordinates, got {}",
+                    coordinates.len()
+                ))),
             },
-            webgpu::GpuExtent3D::ListGpuIntegerCoordinate(_coordinates) => todo!(),
         }
     }
 }
@@ -69,7 +117,7 @@ impl<'a> ToCore<wgpu_core::binding_model::BindingResource<'a>> for webgpu::GpuBi
     }
 }
 
-impl<'a> ToCore<wgpu_core::binding_model::BufferBinding> for webgpu::GpuBufferBinding {
+impl ToCore<wgpu_core::binding_model::BufferBinding> for webgpu::GpuBufferBinding {
     fn to_core(self, table: &ResourceTable) -> wgpu_core::binding_model::BufferBinding {
         let buffer = table.get(&self.buffer).unwrap();
         wgpu_core::binding_model::BufferBinding {
@@ -137,7 +185,7 @@ impl<'a> ToCore<wgpu_core::binding_model::PipelineLayoutDescriptor<'a>>
             bind_group_layouts: self
                 .bind_group_layouts
                 .into_iter()
-                .map(|bind_group_layout| *table.get(&bind_group_layout).unwrap())
+                .map(|bind_group_layout| bind_group_layout.to_core(table))
                 .collect::<Vec<_>>()
                 .into(),
             push_constant_ranges: vec![].into(),
@@ -145,24 +193,64 @@ impl<'a> ToCore<wgpu_core::binding_model::PipelineLayoutDescriptor<'a>>
     }
 }
 
-impl<'a> ToCore<wgpu_core::pipeline::RenderPipelineDescriptor<'a>>
+impl<'a> TryToCore<wgpu_core::pipeline::RenderPipelineDescriptor<'a>>
     for webgpu::GpuRenderPipelineDescriptor
 {
-    fn to_core(self, table: &ResourceTable) -> wgpu_core::pipeline::RenderPipelineDescriptor<'a> {
-        wgpu_core::pipeline::RenderPipelineDescriptor {
+    fn try_to_core(
+        self,
+        table: &ResourceTable,
+    ) -> Result<wgpu_core::pipeline::RenderPipelineDescriptor<'a>, ValidationError> {
+        Ok(wgpu_core::pipeline::RenderPipelineDescriptor {
+            label: self.label.map(|l| l.into()),
             // TODO: remove defaults
-            label: Default::default(),
             layout: self.layout.map(|l| l.to_core(table)),
-            vertex: self.vertex.to_core(table),
-            primitive: self.primitive.map(|p| p.to_core(table)).unwrap(),
+            vertex: self.vertex.try_to_core(table)?,
+            primitive: self.primitive.map(|p| p.to_core(table)).unwrap_or_default(),
             depth_stencil: self.depth_stencil.map(|ds| ds.to_core(table)),
             multisample: self
                 .multisample
                 .map(|ms| ms.to_core(table))
                 .unwrap_or_default(),
-            fragment: self.fragment.map(|f| f.to_core(table)),
+            fragment: self.fragment.map(|f| f.try_to_core(table)).transpose()?,
             multiview: Default::default(),
-        }
+        })
+    }
+}
+
+impl<'a> TryToCore<wgpu_core::pipeline::ComputePipelineDescriptor<'a>>
+    for webgpu::GpuComputePipelineDescriptor
+{
+    fn try_to_core(
+        self,
+        table: &ResourceTable,
+    ) -> Result<wgpu_core::pipeline::ComputePipelineDescriptor<'a>, ValidationError> {
+        Ok(wgpu_core::pipeline::ComputePipelineDescriptor {
+            label: self.label.map(|l| l.into()),
+            layout: self.layout.map(|l| l.to_core(table)),
+            stage: self.compute.try_to_core(table)?,
+        })
+    }
+}
+
+impl<'a> TryToCore<wgpu_core::pipeline::ProgrammableStageDescriptor<'a>>
+    for webgpu::GpuProgrammableStage
+{
+    fn try_to_core(
+        self,
+        table: &ResourceTable,
+    ) -> Result<wgpu_core::pipeline::ProgrammableStageDescriptor<'a>, ValidationError> {
+        validate_pipeline_constants(&self.constants)?;
+        // https://www.w3.org/TR/webgpu/#abstract-opdef-get-the-entry-point
+        // The spec allows leaving this out when the module has a single entry point for the stage, but wgpu-core 0.18 has no way to list a module's entry points.
+        let entry_point = self.entry_point.ok_or_else(|| {
+            ValidationError(
+                "entry-point is required, the module's entry points can't be looked up".to_string(),
+            )
+        })?;
+        Ok(wgpu_core::pipeline::ProgrammableStageDescriptor {
+            module: table.get(&self.module).unwrap().shader_module,
+            entry_point: entry_point.into(),
+        })
     }
 }
 
@@ -180,7 +268,7 @@ impl ToCore<wgpu_types::DepthStencilState> for webgpu::GpuDepthStencilState {
     fn to_core(self, table: &ResourceTable) -> wgpu_types::DepthStencilState {
         wgpu_types::DepthStencilState {
             format: self.format.into(),
-            depth_write_enabled: self.depth_write_enabled.unwrap().into(),
+            depth_write_enabled: self.depth_write_enabled.unwrap(),
             depth_compare: self.depth_compare.unwrap().into(),
             stencil: wgpu_types::StencilState {
                 front: self
@@ -241,11 +329,15 @@ impl ToCore<wgpu_types::PrimitiveState> for webgpu::GpuPrimitiveState {
     }
 }
 
-impl<'a> ToCore<wgpu_core::pipeline::FragmentState<'a>> for webgpu::GpuFragmentState {
-    fn to_core(self, table: &ResourceTable) -> wgpu_core::pipeline::FragmentState<'a> {
-        wgpu_core::pipeline::FragmentState {
+impl<'a> TryToCore<wgpu_core::pipeline::FragmentState<'a>> for webgpu::GpuFragmentState {
+    fn try_to_core(
+        self,
+        table: &ResourceTable,
+    ) -> Result<wgpu_core::pipeline::FragmentState<'a>, ValidationError> {
+        validate_pipeline_constants(&self.constants)?;
+        Ok(wgpu_core::pipeline::FragmentState {
             stage: wgpu_core::pipeline::ProgrammableStageDescriptor {
-                module: self.module.to_core(table),
+                module: table.get(&self.module).unwrap().shader_module,
                 entry_point: self.entry_point.into(),
             },
             targets: self
@@ -254,7 +346,7 @@ impl<'a> ToCore<wgpu_core::pipeline::FragmentState<'a>> for webgpu::GpuFragmentS
                 .map(|t| Some(t.to_core(table)))
                 .collect::<Vec<_>>()
                 .into(),
-        }
+        })
     }
 }
 
@@ -300,18 +392,22 @@ impl ToCore<wgpu_types::BlendComponent> for webgpu::GpuBlendComponent {
     }
 }
 
-impl<'a> ToCore<wgpu_core::pipeline::VertexState<'a>> for webgpu::GpuVertexState {
-    fn to_core(self, table: &ResourceTable) -> wgpu_core::pipeline::VertexState<'a> {
-        wgpu_core::pipeline::VertexState {
+impl<'a> TryToCore<wgpu_core::pipeline::VertexState<'a>> for webgpu::GpuVertexState {
+    fn try_to_core(
+        self,
+        table: &ResourceTable,
+    ) -> Result<wgpu_core::pipeline::VertexState<'a>, ValidationError> {
+        validate_pipeline_constants(&self.constants)?;
+        Ok(wgpu_core::pipeline::VertexState {
             stage: wgpu_core::pipeline::ProgrammableStageDescriptor {
-                module: self.module.to_core(table),
+                module: table.get(&self.module).unwrap().shader_module,
                 entry_point: self.entry_point.into(),
             },
             buffers: self
                 .buffers
                 .map(|buffer| buffer.into_iter().map(|b| b.to_core(table)).collect())
                 .unwrap_or_default(),
-        }
+        })
     }
 }
 
@@ -339,21 +435,33 @@ impl ToCore<wgpu_types::VertexAttribute> for webgpu::GpuVertexAttribute {
     }
 }
 
-impl<'a> ToCore<wgpu_types::TextureDescriptor<wgpu_core::Label<'a>, Vec<wgpu_types::TextureFormat>>>
+pub fn texture_usages_to_core(
+    usage: webgpu::GpuTextureUsageFlags,
+) -> Result<wgpu_types::TextureUsages, ValidationError> {
+    wgpu_types::TextureUsages::from_bits(usage)
+        .ok_or_else(|| ValidationError(format!("{usage:#x} has unknown texture usage bits")))
+}
+
+impl<'a>
+    TryToCore<wgpu_types::TextureDescriptor<wgpu_core::Label<'a>, Vec<wgpu_types::TextureFormat>>>
     for webgpu::GpuTextureDescriptor
 {
-    fn to_core(
+    fn try_to_core(
         self,
         table: &ResourceTable,
-    ) -> wgpu_types::TextureDescriptor<wgpu_core::Label<'a>, Vec<wgpu_types::TextureFormat>> {
-        wgpu_types::TextureDescriptor {
+    ) -> Result<
+        wgpu_types::TextureDescriptor<wgpu_core::Label<'a>, Vec<wgpu_types::TextureFormat>>,
+        ValidationError,
+    > {
+        // https://www.w3.org/TR/webgpu/#dictdef-gputexturedescriptor
+        Ok(wgpu_types::TextureDescriptor {
             label: self.label.map(|l| l.into()),
-            size: self.size.to_core(table),
-            mip_level_count: self.mip_level_count.unwrap(),
-            sample_count: self.sample_count.unwrap(),
+            size: self.size.try_to_core(table)?,
+            mip_level_count: self.mip_level_count.unwrap_or(1),
+            sample_count: self.sample_count.unwrap_or(1),
             dimension: self.dimension.into(),
             format: self.format.into(),
-            usage: wgpu_types::TextureUsages::from_bits(self.usage).unwrap(),
+            usage: texture_usages_to_core(self.usage)?,
             view_formats: self
                 .view_formats
                 .map(|view_formats| {
@@ -363,7 +471,7 @@ impl<'a> ToCore<wgpu_types::TextureDescriptor<wgpu_core::Label<'a>, Vec<wgpu_typ
                         .collect()
                 })
                 .unwrap_or_default(),
-        }
+        })
     }
 }
 
@@ -422,7 +530,7 @@ impl<'a> ToCore<wgpu_core::binding_model::BindGroupLayoutDescriptor<'a>>
 impl ToCore<wgpu_types::BindGroupLayoutEntry> for webgpu::GpuBindGroupLayoutEntry {
     fn to_core(self, table: &ResourceTable) -> wgpu_types::BindGroupLayoutEntry {
         wgpu_types::BindGroupLayoutEntry {
-            binding: self.binding.into(),
+            binding: self.binding,
             // TODO:
             visibility: wgpu_types::ShaderStages::from_bits(self.visibility).unwrap(),
             ty: match (
@@ -484,6 +592,139 @@ impl ToCore<wgpu_types::BindingType> for webgpu::GpuExternalTextureBindingLayout
     }
 }
 
+impl<'a> ToCore<wgpu_types::QuerySetDescriptor<wgpu_core::Label<'a>>>
+    for webgpu::GpuQuerySetDescriptor
+{
+    fn to_core(
+        self,
+        _table: &ResourceTable,
+    ) -> wgpu_types::QuerySetDescriptor<wgpu_core::Label<'a>> {
+        wgpu_types::QuerySetDescriptor {
+            label: self.label.map(|l| l.into()),
+            ty: self.type_.into(),
+            count: self.count,
+        }
+    }
+}
+
+// https://www.w3.org/TR/webgpu/#abstract-opdef-validating-gpuprogrammablestage
+// TODO: pass the constants on once wgpu-core takes them. Until then there's nothing to override, since naga 0.14 can't parse override declarations.
+fn validate_pipeline_constants(
+    constants: &Option<Vec<(String, f64)>>,
+) -> Result<(), ValidationError> {
+    match constants.iter().flatten().next() {
+        Some((key, _)) => Err(ValidationError(format!(
+            "shader has no pipeline-overridable constant named {key}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+// https://www.w3.org/TR/webgpu/#abstract-opdef-validate-timestampwrites
+fn validate_timestamp_writes(
+    query_set: &super::QuerySet,
+    beginning_of_pass_write_index: Option<u32>,
+    end_of_pass_write_index: Option<u32>,
+) -> Result<(), ValidationError> {
+    // A timestamp query set can only be created with the timestamp-query feature,
+    // so this is what gates timestamp writes on it.
+    if query_set.type_ != webgpu::GpuQueryType::Timestamp {
+        return Err(ValidationError(
+            "timestamp writes need a query set of type timestamp".to_string(),
+        ));
+    }
+    if beginning_of_pass_write_index.is_some()
+        && beginning_of_pass_write_index == end_of_pass_write_index
+    {
+        return Err(ValidationError(
+            "timestamp writes can't use the same index twice".to_string(),
+        ));
+    }
+    for index in [beginning_of_pass_write_index, end_of_pass_write_index]
+        .into_iter()
+        .flatten()
+    {
+        if index >= query_set.count {
+            return Err(ValidationError(format!(
+                "timestamp write index {index} is out of bounds for a query set of {} queries",
+                query_set.count
+            )));
+        }
+    }
+    Ok(())
+}
+
+impl TryToCore<wgpu_core::command::RenderPassTimestampWrites>
+    for webgpu::GpuRenderPassTimestampWrites
+{
+    fn try_to_core(
+        self,
+        table: &ResourceTable,
+    ) -> Result<wgpu_core::command::RenderPassTimestampWrites, ValidationError> {
+        let query_set = table.get(&self.query_set).unwrap();
+        validate_timestamp_writes(
+            query_set,
+            self.beginning_of_pass_write_index,
+            self.end_of_pass_write_index,
+        )?;
+        Ok(wgpu_core::command::RenderPassTimestampWrites {
+            query_set: query_set.id()?,
+            beginning_of_pass_write_index: self.beginning_of_pass_write_index,
+            end_of_pass_write_index: self.end_of_pass_write_index,
+        })
+    }
+}
+
+impl TryToCore<wgpu_core::command::ComputePassTimestampWrites>
+    for webgpu::GpuComputePassTimestampWrites
+{
+    fn try_to_core(
+        self,
+        table: &ResourceTable,
+    ) -> Result<wgpu_core::command::ComputePassTimestampWrites, ValidationError> {
+        let query_set = table.get(&self.query_set).unwrap();
+        validate_timestamp_writes(
+            query_set,
+            self.beginning_of_pass_write_index,
+            self.end_of_pass_write_index,
+        )?;
+        Ok(wgpu_core::command::ComputePassTimestampWrites {
+            query_set: query_set.id()?,
+            beginning_of_pass_write_index: self.beginning_of_pass_write_index,
+            end_of_pass_write_index: self.end_of_pass_write_index,
+        })
+    }
+}
+
+impl<'a> ToCore<wgpu_core::command::RenderBundleEncoderDescriptor<'a>>
+    for webgpu::GpuRenderBundleEncoderDescriptor
+{
+    fn to_core(
+        self,
+        _table: &ResourceTable,
+    ) -> wgpu_core::command::RenderBundleEncoderDescriptor<'a> {
+        // https://www.w3.org/TR/webgpu/#dictdef-gpurenderbundleencoderdescriptor
+        wgpu_core::command::RenderBundleEncoderDescriptor {
+            label: self.label.map(|l| l.into()),
+            color_formats: self
+                .color_formats
+                .into_iter()
+                .map(|f| Some(f.into()))
+                .collect::<Vec<_>>()
+                .into(),
+            depth_stencil: self.depth_stencil_format.map(|format| {
+                wgpu_types::RenderBundleDepthStencil {
+                    format: format.into(),
+                    depth_read_only: self.depth_read_only.unwrap_or(false),
+                    stencil_read_only: self.stencil_read_only.unwrap_or(false),
+                }
+            }),
+            sample_count: self.sample_count.unwrap_or(1),
+            multiview: None,
+        }
+    }
+}
+
 // see begin_render_pass
 // impl<'a> ToCore<wgpu_core::command::RenderPassDescriptor<'a>> for webgpu::GpuRenderPassDescriptor {
 //     fn to_core(self, table: &ResourceTable) -> wgpu_core::command::RenderPassDescriptor<'a> {
@@ -516,14 +757,14 @@ impl ToCore<wgpu_core::command::RenderPassDepthStencilAttachment>
             depth: pass_channel_from_options(
                 self.depth_load_op.map(|x| x.into()),
                 self.depth_store_op.map(|x| x.into()),
-                self.depth_clear_value.map(|x| x.into()),
-                self.depth_read_only.map(|x| x.into()),
+                self.depth_clear_value,
+                self.depth_read_only,
             ),
             stencil: pass_channel_from_options(
                 self.stencil_load_op.map(|x| x.into()),
                 self.stencil_store_op.map(|x| x.into()),
-                self.stencil_clear_value.map(|x| x.into()),
-                self.stencil_read_only.map(|x| x.into()),
+                self.stencil_clear_value,
+                self.stencil_read_only,
             ),
         }
     }
@@ -588,46 +829,246 @@ impl<'a> ToCore<wgpu_types::BufferDescriptor<wgpu_core::Label<'a>>>
     }
 }
 
-impl<'a> ToCore<wgpu_types::DeviceDescriptor<wgpu_core::Label<'a>>>
+impl<'a> TryToCore<wgpu_types::DeviceDescriptor<wgpu_core::Label<'a>>>
     for webgpu::GpuDeviceDescriptor
 {
-    fn to_core(self, _table: &ResourceTable) -> wgpu_types::DeviceDescriptor<wgpu_core::Label<'a>> {
-        wgpu_types::DeviceDescriptor {
+    fn try_to_core(
+        self,
+        _table: &ResourceTable,
+    ) -> Result<wgpu_types::DeviceDescriptor<wgpu_core::Label<'a>>, ValidationError> {
+        // https://www.w3.org/TR/webgpu/#gpudevicedescriptor
+        let mut features = wgpu_types::Features::empty();
+        for feature in self.required_features.unwrap_or_default() {
+            features |= feature_to_core(feature)?;
+        }
+        // wgpu's default limits are the same as webgpu's.
+        let mut limits = wgpu_types::Limits::default();
+        for (name, value) in self.required_limits.unwrap_or_default() {
+            require_limit(&mut limits, &name, value)?;
+        }
+        Ok(wgpu_types::DeviceDescriptor {
             label: self.label.map(|l| l.into()),
-            // TODO: Don't default
-            ..Default::default()
+            features,
+            limits,
+        })
+    }
+}
+
+fn feature_to_core(
+    feature: webgpu::GpuFeatureName,
+) -> Result<wgpu_types::Features, ValidationError> {
+    Ok(match feature {
+        webgpu::GpuFeatureName::DepthClipControl => wgpu_types::Features::DEPTH_CLIP_CONTROL,
+        webgpu::GpuFeatureName::Depth32floatStencil8 => wgpu_types::Features::DEPTH32FLOAT_STENCIL8,
+        webgpu::GpuFeatureName::TextureCompressionBc => {
+            wgpu_types::Features::TEXTURE_COMPRESSION_BC
+        }
+        webgpu::GpuFeatureName::TextureCompressionEtc2 => {
+            wgpu_types::Features::TEXTURE_COMPRESSION_ETC2
+        }
+        webgpu::GpuFeatureName::TextureCompressionAstc => {
+            wgpu_types::Features::TEXTURE_COMPRESSION_ASTC
+        }
+        webgpu::GpuFeatureName::TimestampQuery => wgpu_types::Features::TIMESTAMP_QUERY,
+        webgpu::GpuFeatureName::IndirectFirstInstance => {
+            wgpu_types::Features::INDIRECT_FIRST_INSTANCE
         }
+        webgpu::GpuFeatureName::ShaderF16 => wgpu_types::Features::SHADER_F16,
+        webgpu::GpuFeatureName::Rg11b10ufloatRenderable => {
+            wgpu_types::Features::RG11B10UFLOAT_RENDERABLE
+        }
+        webgpu::GpuFeatureName::Bgra8unormStorage => wgpu_types::Features::BGRA8UNORM_STORAGE,
+        // not in wgpu 0.18 yet.
+        webgpu::GpuFeatureName::Float32Filterable => {
+            return Err(ValidationError(
+                "float32-filterable is not supported".to_string(),
+            ))
+        }
+    })
+}
+
+// https://www.w3.org/TR/webgpu/#limits
+// Values worse than the default are ignored, so only ever raise maximums and lower alignments.
+fn require_limit(
+    limits: &mut wgpu_types::Limits,
+    name: &str,
+    value: u64,
+) -> Result<(), ValidationError> {
+    fn raise(limit: &mut u32, value: u64) {
+        *limit = (*limit as u64).max(value).min(u32::MAX as u64) as u32;
     }
+    fn lower_alignment(limit: &mut u32, value: u64) -> Result<(), ValidationError> {
+        if !value.is_power_of_two() {
+            return Err(ValidationError(format!(
+                "alignment limits have to be a power of two, got {value}"
+            )));
+        }
+        *limit = (*limit as u64).min(value) as u32;
+        Ok(())
+    }
+    // wgpu 0.18 can't raise these, so anything above the webgpu default is out of reach.
+    fn fixed(name: &str, default: u32, value: u64) -> Result<(), ValidationError> {
+        if value > default as u64 {
+            return Err(ValidationError(format!(
+                "{name} can't be raised above {default}, got {value}"
+            )));
+        }
+        Ok(())
+    }
+
+    match name {
+        "maxTextureDimension1D" => raise(&mut limits.max_texture_dimension_1d, value),
+        "maxTextureDimension2D" => raise(&mut limits.max_texture_dimension_2d, value),
+        "maxTextureDimension3D" => raise(&mut limits.max_texture_dimension_3d, value),
+        "maxTextureArrayLayers" => raise(&mut limits.max_texture_array_layers, value),
+        "maxBindGroups" => raise(&mut limits.max_bind_groups, value),
+        "maxBindGroupsPlusVertexBuffers" => {
+            fixed(name, super::MAX_BIND_GROUPS_PLUS_VERTEX_BUFFERS, value)?
+        }
+        "maxBindingsPerBindGroup" => raise(&mut limits.max_bindings_per_bind_group, value),
+        "maxDynamicUniformBuffersPerPipelineLayout" => raise(
+            &mut limits.max_dynamic_uniform_buffers_per_pipeline_layout,
+            value,
+        ),
+        "maxDynamicStorageBuffersPerPipelineLayout" => raise(
+            &mut limits.max_dynamic_storage_buffers_per_pipeline_layout,
+            value,
+        ),
+        "maxSampledTexturesPerShaderStage" => {
+            raise(&mut limits.max_sampled_textures_per_shader_stage, value)
+        }
+        "maxSamplersPerShaderStage" => raise(&mut limits.max_samplers_per_shader_stage, value),
+        "maxStorageBuffersPerShaderStage" => {
+            raise(&mut limits.max_storage_buffers_per_shader_stage, value)
+        }
+        "maxStorageTexturesPerShaderStage" => {
+            raise(&mut limits.max_storage_textures_per_shader_stage, value)
+        }
+        "maxUniformBuffersPerShaderStage" => {
+            raise(&mut limits.max_uniform_buffers_per_shader_stage, value)
+        }
+        "maxUniformBufferBindingSize" => raise(&mut limits.max_uniform_buffer_binding_size, value),
+        "maxStorageBufferBindingSize" => raise(&mut limits.max_storage_buffer_binding_size, value),
+        "minUniformBufferOffsetAlignment" => {
+            lower_alignment(&mut limits.min_uniform_buffer_offset_alignment, value)?
+        }
+        "minStorageBufferOffsetAlignment" => {
+            lower_alignment(&mut limits.min_storage_buffer_offset_alignment, value)?
+        }
+        "maxVertexBuffers" => raise(&mut limits.max_vertex_buffers, value),
+        "maxBufferSize" => limits.max_buffer_size = limits.max_buffer_size.max(value),
+        "maxVertexAttributes" => raise(&mut limits.max_vertex_attributes, value),
+        "maxVertexBufferArrayStride" => raise(&mut limits.max_vertex_buffer_array_stride, value),
+        "maxInterStageShaderComponents" => {
+            raise(&mut limits.max_inter_stage_shader_components, value)
+        }
+        "maxInterStageShaderVariables" => {
+            fixed(name, super::MAX_INTER_STAGE_SHADER_VARIABLES, value)?
+        }
+        "maxColorAttachments" => fixed(name, super::MAX_COLOR_ATTACHMENTS, value)?,
+        "maxColorAttachmentBytesPerSample" => {
+            fixed(name, super::MAX_COLOR_ATTACHMENT_BYTES_PER_SAMPLE, value)?
+        }
+        "maxComputeWorkgroupStorageSize" => {
+            raise(&mut limits.max_compute_workgroup_storage_size, value)
+        }
+        "maxComputeInvocationsPerWorkgroup" => {
+            raise(&mut limits.max_compute_invocations_per_workgroup, value)
+        }
+        "maxComputeWorkgroupSizeX" => raise(&mut limits.max_compute_workgroup_size_x, value),
+        "maxComputeWorkgroupSizeY" => raise(&mut limits.max_compute_workgroup_size_y, value),
+        "maxComputeWorkgroupSizeZ" => raise(&mut limits.max_compute_workgroup_size_z, value),
+        "maxComputeWorkgroupsPerDimension" => {
+            raise(&mut limits.max_compute_workgroups_per_dimension, value)
+        }
+        _ => return Err(ValidationError(format!("unknown limit {name}"))),
+    }
+    Ok(())
 }
 
-impl ToCore<wgpu_types::ImageCopyTexture<wgpu_core::id::TextureId>>
+impl ToCore<wgpu_core::instance::RequestAdapterOptions> for webgpu::GpuRequestAdapterOptions {
+    fn to_core(self, _table: &ResourceTable) -> wgpu_core::instance::RequestAdapterOptions {
+        // https://www.w3.org/TR/webgpu/#dictdef-gpurequestadapteroptions
+        wgpu_core::instance::RequestAdapterOptions {
+            power_preference: self
+                .power_preference
+                .map(|p| p.into())
+                .unwrap_or(wgpu_types::PowerPreference::None),
+            force_fallback_adapter: self.force_fallback_adapter.unwrap_or(false),
+            compatible_surface: None,
+        }
+    }
+}
+
+impl TryToCore<wgpu_types::ImageCopyTexture<wgpu_core::id::TextureId>>
     for webgpu::GpuImageCopyTexture
 {
-    fn to_core(
+    fn try_to_core(
         self,
         table: &ResourceTable,
-    ) -> wgpu_types::ImageCopyTexture<wgpu_core::id::TextureId> {
-        wgpu_types::ImageCopyTexture {
-            texture: self.texture.to_core(table),
-            mip_level: self.mip_level.unwrap(),
-            origin: self.origin.unwrap().to_core(table),
-            aspect: self.aspect.unwrap().into(),
-        }
+    ) -> Result<wgpu_types::ImageCopyTexture<wgpu_core::id::TextureId>, ValidationError> {
+        // https://www.w3.org/TR/webgpu/#dictdef-gpuimagecopytexture
+        Ok(wgpu_types::ImageCopyTexture {
+            texture: table.get(&self.texture).unwrap().texture,
+            mip_level: self.mip_level.unwrap_or(0),
+            origin: self
+                .origin
+                .map(|origin| origin.try_to_core(table))
+                .transpose()?
+                .unwrap_or(wgpu_types::Origin3d::ZERO),
+            aspect: self
+                .aspect
+                .map(|aspect| aspect.into())
+                .unwrap_or(wgpu_types::TextureAspect::All),
+        })
+    }
+}
+
+impl TryToCore<wgpu_types::ImageCopyBuffer<wgpu_core::id::BufferId>>
+    for webgpu::GpuImageCopyBuffer
+{
+    fn try_to_core(
+        self,
+        table: &ResourceTable,
+    ) -> Result<wgpu_types::ImageCopyBuffer<wgpu_core::id::BufferId>, ValidationError> {
+        let buffer = table.get(&self.buffer).unwrap();
+        // https://www.w3.org/TR/webgpu/#dictdef-gpuimagecopybuffer
+        Ok(wgpu_types::ImageCopyBuffer {
+            buffer: buffer.buffer,
+            layout: wgpu_types::ImageDataLayout {
+                offset: self.offset.unwrap_or(0),
+                bytes_per_row: self.bytes_per_row,
+                rows_per_image: self.rows_per_image,
+            },
+        })
     }
 }
 
-impl ToCore<wgpu_types::Origin3d> for webgpu::GpuOrigin3DDictOrListGpuIntegerCoordinate {
-    fn to_core(self, _table: &ResourceTable) -> wgpu_types::Origin3d {
+impl TryToCore<wgpu_types::Origin3d> for webgpu::GpuOrigin3DDictOrListGpuIntegerCoordinate {
+    fn try_to_core(self, _table: &ResourceTable) -> Result<wgpu_types::Origin3d, ValidationError> {
+        // https://www.w3.org/TR/webgpu/#dictdef-gpuorigin3ddict
         match self {
             webgpu::GpuOrigin3DDictOrListGpuIntegerCoordinate::GpuOrigin3DDict(dict) => {
-                wgpu_types::Origin3d {
-                    x: dict.x.unwrap(),
-                    y: dict.y.unwrap(),
-                    z: dict.z.unwrap(),
-                }
+                Ok(wgpu_types::Origin3d {
+                    x: dict.x.unwrap_or(0),
+                    y: dict.y.unwrap_or(0),
+                    z: dict.z.unwrap_or(0),
+                })
             }
-            webgpu::GpuOrigin3DDictOrListGpuIntegerCoordinate::ListGpuIntegerCoordinate(_) => {
-                todo!()
+            webgpu::GpuOrigin3DDictOrListGpuIntegerCoordinate::ListGpuIntegerCoordinate(
+                coordinates,
+            ) => {
+                if coordinates.len() > 3 {
+                    return Err(ValidationError(format!(
+                        "GPUOrigin3D can have at most 3 coordinates, got {}",
+                        coordinates.len()
+                    )));
+                }
+                Ok(wgpu_types::Origin3d {
+                    x: coordinates.first().copied().unwrap_or(0),
+                    y: coordinates.get(1).copied().unwrap_or(0),
+                    z: coordinates.get(2).copied().unwrap_or(0),
+                })
             }
         }
     }
@@ -642,3 +1083,124 @@ impl ToCore<wgpu_types::ImageDataLayout> for webgpu::GpuImageDataLayout {
         }
     }
 }
+
+/// Converts the arguments shared by all image copies, failing on the first one that doesn't make sense.
+pub fn image_copy_to_core<S, D, SourceCore, DestinationCore>(
+    table: &ResourceTable,
+    source: S,
+    destination: D,
+    copy_size: webgpu::GpuExtent3D,
+) -> Result<(SourceCore, DestinationCore, wgpu_types::Extent3d), ValidationError>
+where
+    S: TryToCore<SourceCore>,
+    D: TryToCore<DestinationCore>,
+{
+    Ok((
+        source.try_to_core(table)?,
+        destination.try_to_core(table)?,
+        copy_size.try_to_core(table)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use wgpu_core::id::TypedId;
+
+    use super::*;
+
+    fn timestamp_query_set(count: u32) -> super::super::QuerySet {
+        super::super::QuerySet {
+            query_set: wgpu_core::id::QuerySetId::zip(0, 1, wgpu_types::Backend::Vulkan),
+            valid: true,
+            destroyed: false,
+            type_: webgpu::GpuQueryType::Timestamp,
+            count,
+            label: String::new(),
+        }
+    }
+
+    #[test]
+    fn require_limit_raises_and_lowers() {
+        let mut limits = wgpu_types::Limits::default();
+        require_limit(&mut limits, "maxBindGroups", 6).unwrap();
+        assert_eq!(limits.max_bind_groups, 6);
+        // asking for less than the default keeps the default.
+        require_limit(&mut limits, "maxVertexBuffers", 1).unwrap();
+        assert_eq!(
+            limits.max_vertex_buffers,
+            wgpu_types::Limits::default().max_vertex_buffers
+        );
+        require_limit(&mut limits, "minUniformBufferOffsetAlignment", 64).unwrap();
+        assert_eq!(limits.min_uniform_buffer_offset_alignment, 64);
+    }
+
+    #[test]
+    fn require_limit_rejects_bad_limits() {
+        let mut limits = wgpu_types::Limits::default();
+        assert!(require_limit(&mut limits, "maxWhatever", 1).is_err());
+        assert!(require_limit(&mut limits, "minStorageBufferOffsetAlignment", 48).is_err());
+        assert!(require_limit(&mut limits, "maxColorAttachments", u64::MAX).is_err());
+    }
+
+    #[test]
+    fn validate_timestamp_writes_checks_indices() {
+        let query_set = timestamp_query_set(2);
+        assert!(validate_timestamp_writes(&query_set, Some(0), Some(1)).is_ok());
+        assert!(validate_timestamp_writes(&query_set, None, None).is_ok());
+        assert!(validate_timestamp_writes(&query_set, Some(1), Some(1)).is_err());
+        assert!(validate_timestamp_writes(&query_set, None, Some(2)).is_err());
+    }
+
+    #[test]
+    fn validate_timestamp_writes_needs_a_timestamp_query_set() {
+        let query_set = super::super::QuerySet {
+            type_: webgpu::GpuQueryType::Occlusion,
+            ..timestamp_query_set(2)
+        };
+        assert!(validate_timestamp_writes(&query_set, Some(0), None).is_err());
+    }
+
+    #[test]
+    fn extent_list_fills_in_missing_dimensions() {
+        let table = ResourceTable::new();
+        let extent = |coordinates: Vec<u32>| {
+            webgpu::GpuExtent3D::ListGpuIntegerCoordinate(coordinates).try_to_core(&table)
+        };
+        assert_eq!(
+            extent(vec![4]).unwrap(),
+            wgpu_types::Extent3d {
+                width: 4,
+                height: 1,
+                depth_or_array_layers: 1,
+            }
+        );
+        assert_eq!(
+            extent(vec![4, 5, 6]).unwrap(),
+            wgpu_types::Extent3d {
+                width: 4,
+                height: 5,
+                depth_or_array_layers: 6,
+            }
+        );
+        assert!(extent(vec![]).is_err());
+        assert!(extent(vec![1, 2, 3, 4]).is_err());
+    }
+
+    #[test]
+    fn origin_list_fills_in_missing_coordinates() {
+        let table = ResourceTable::new();
+        let origin = |coordinates: Vec<u32>| {
+            webgpu::GpuOrigin3DDictOrListGpuIntegerCoordinate::ListGpuIntegerCoordinate(coordinates)
+                .try_to_core(&table)
+        };
+        assert_eq!(
+            origin(vec![]).unwrap(),
+            wgpu_types::Origin3d { x: 0, y: 0, z: 0 }
+        );
+        assert_eq!(
+            origin(vec![1, 2]).unwrap(),
+            wgpu_types::Origin3d { x: 1, y: 2, z: 0 }
+        );
+        assert!(origin(vec![1, 2, 3, 4]).is_err());
+    }
+}